@@ -0,0 +1,358 @@
+use super::{
+    stack, Air, EvaluationFrame, ProcessorAir, ProofOptions, PublicInputs, TraceInfo,
+    TraceMetadata, VmTransition,
+};
+use core::fmt;
+use vm_core::{
+    opcodes::{FlowOps, UserOps},
+    BaseElement, FieldElement, StarkField, TraceState,
+};
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+const FLOW_OPS: [FlowOps; 8] = [
+    FlowOps::Hacc,
+    FlowOps::Begin,
+    FlowOps::Tend,
+    FlowOps::Fend,
+    FlowOps::Loop,
+    FlowOps::Wrap,
+    FlowOps::Break,
+    FlowOps::Void,
+];
+
+const USER_OPS: [UserOps; 36] = [
+    UserOps::Begin,
+    UserOps::Noop,
+    UserOps::Assert,
+    UserOps::AssertEq,
+    UserOps::Push,
+    UserOps::Read,
+    UserOps::Read2,
+    UserOps::Clk,
+    UserOps::SDepth,
+    UserOps::Dup,
+    UserOps::Dup2,
+    UserOps::Dup4,
+    UserOps::Pad2,
+    UserOps::Drop,
+    UserOps::Drop4,
+    UserOps::Swap,
+    UserOps::Swap2,
+    UserOps::Swap4,
+    UserOps::Roll4,
+    UserOps::Roll8,
+    UserOps::Choose,
+    UserOps::Choose2,
+    UserOps::CSwap,
+    UserOps::CSwap2,
+    UserOps::CSwapW,
+    UserOps::Add,
+    UserOps::Mul,
+    UserOps::Inv,
+    UserOps::Neg,
+    UserOps::Not,
+    UserOps::And,
+    UserOps::Or,
+    UserOps::Eq,
+    UserOps::Cmp,
+    UserOps::BinAcc,
+    UserOps::RescR,
+];
+
+// CONSTRAINT FAILURE
+// ================================================================================================
+
+/// Describes the first constraint of [ProcessorAir] which was not satisfied by an execution trace.
+#[derive(Clone, PartialEq)]
+pub enum ConstraintFailure {
+    /// A boundary assertion did not hold; `register` and `step` identify the trace cell.
+    Assertion {
+        register: usize,
+        step: usize,
+        expected: BaseElement,
+        actual: BaseElement,
+    },
+    /// A transition constraint did not evaluate to zero between rows `step` and `step + 1`;
+    /// `index` is the position of the constraint within its component.
+    Transition {
+        component: ConstraintComponent,
+        index: usize,
+        step: usize,
+        evaluation: BaseElement,
+        current: TraceState<BaseElement>,
+        next: TraceState<BaseElement>,
+    },
+}
+
+/// Part of the AIR a transition constraint belongs to.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ConstraintComponent {
+    Decoder,
+    StackAux,
+    Stack,
+}
+
+impl fmt::Debug for ConstraintFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl fmt::Display for ConstraintFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConstraintFailure::Assertion {
+                register,
+                step,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "assertion against register {} at step {} failed: expected {}, but was {}",
+                register,
+                step,
+                expected.as_int(),
+                actual.as_int()
+            ),
+            ConstraintFailure::Transition {
+                component,
+                index,
+                step,
+                evaluation,
+                current,
+                next,
+            } => write!(
+                f,
+                "{:?} transition constraint {} evaluated to {} at step {}\n  current: {:?}\n  next:    {:?}",
+                component,
+                index,
+                evaluation.as_int(),
+                step,
+                current,
+                next
+            ),
+        }
+    }
+}
+
+// CONSTRAINT CHECKER
+// ================================================================================================
+
+/// Evaluates all assertions and transition constraints of [ProcessorAir] against a concrete
+/// execution trace, and returns the first constraint which was not satisfied.
+///
+/// Rows of the trace are read via `read_row` which must fill the provided slice with values of
+/// all registers at the specified step (e.g. `ExecutionTrace::read_row_into()`). Unlike the
+/// prover, this does not commit to the trace or build any polynomials, and thus is useful for
+/// pinpointing divergences between the processor and the AIR.
+pub fn check_trace<R>(
+    trace_info: TraceInfo,
+    pub_inputs: PublicInputs,
+    read_row: R,
+) -> Result<(), ConstraintFailure>
+where
+    R: Fn(usize, &mut [BaseElement]),
+{
+    let trace_width = trace_info.width();
+    let trace_length = trace_info.length();
+    let air = ProcessorAir::new(trace_info, pub_inputs, ProofOptions::default().into_inner());
+
+    // --- check assertions -----------------------------------------------------------------------
+    let mut row = vec![BaseElement::ZERO; trace_width];
+    for assertion in air.get_assertions() {
+        let mut failure = None;
+        assertion.apply(trace_length, |step, expected| {
+            if failure.is_some() {
+                return;
+            }
+            read_row(step, &mut row);
+            let actual = row[assertion.register()];
+            if actual != expected {
+                failure = Some(ConstraintFailure::Assertion {
+                    register: assertion.register(),
+                    step,
+                    expected,
+                    actual,
+                });
+            }
+        });
+
+        if let Some(failure) = failure {
+            return Err(failure);
+        }
+    }
+
+    // --- check transition constraints -----------------------------------------------------------
+    let periodic_columns = air.get_periodic_column_values();
+    let mut periodic_values = vec![BaseElement::ZERO; periodic_columns.len()];
+    let num_constraints =
+        air.decoder_constraint_count + stack::NUM_AUX_CONSTRAINTS + air.stack_depth;
+    let mut evaluations = vec![BaseElement::ZERO; num_constraints];
+
+    let mut current = vec![BaseElement::ZERO; trace_width];
+    let mut next = vec![BaseElement::ZERO; trace_width];
+    read_row(0, &mut next);
+    for step in 0..(trace_length - 1) {
+        current.copy_from_slice(&next);
+        read_row(step + 1, &mut next);
+
+        for (value, column) in periodic_values.iter_mut().zip(periodic_columns.iter()) {
+            *value = column[step % column.len()];
+        }
+
+        let frame = EvaluationFrame::from_rows(current.clone(), next.clone());
+        evaluations.fill(BaseElement::ZERO);
+        air.evaluate_transition(&frame, &periodic_values, &mut evaluations);
+
+        if let Some(i) = evaluations.iter().position(|&e| e != BaseElement::ZERO) {
+            let (component, index) = air.locate_constraint(i);
+            return Err(ConstraintFailure::Transition {
+                component,
+                index,
+                step,
+                evaluation: evaluations[i],
+                current: air.build_state(&current),
+                next: air.build_state(&next),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+// CONSTRAINT COVERAGE
+// ================================================================================================
+
+/// Tracks how many times constraints of each operation were activated (i.e. the operation flag
+/// was non-zero) across a set of execution traces.
+///
+/// Operations which were never activated point to constraints which are not exercised by any of
+/// the traces, and thus are not tested by them.
+pub struct ConstraintCoverage {
+    flow_ops: Vec<(FlowOps, usize)>,
+    user_ops: Vec<(UserOps, usize)>,
+}
+
+impl ConstraintCoverage {
+    /// Returns a new coverage report with activation counts for all operations set to 0.
+    pub fn new() -> Self {
+        ConstraintCoverage {
+            flow_ops: FLOW_OPS.iter().map(|&op| (op, 0)).collect(),
+            user_ops: USER_OPS.iter().map(|&op| (op, 0)).collect(),
+        }
+    }
+
+    /// Updates activation counts with operations executed in the specified execution trace.
+    ///
+    /// Rows of the trace are read via `read_row` which must fill the provided slice with values
+    /// of all registers at the specified step (e.g. `ExecutionTrace::read_row_into()`).
+    pub fn add_trace<R>(&mut self, trace_info: &TraceInfo, read_row: R)
+    where
+        R: Fn(usize, &mut [BaseElement]),
+    {
+        let meta = TraceMetadata::from_trace_info(trace_info);
+        let mut transition = VmTransition::new(meta.ctx_depth, meta.loop_depth, meta.stack_depth);
+
+        let mut current = vec![BaseElement::ZERO; trace_info.width()];
+        let mut next = vec![BaseElement::ZERO; trace_info.width()];
+        read_row(0, &mut next);
+        for step in 0..(trace_info.length() - 1) {
+            current.copy_from_slice(&next);
+            read_row(step + 1, &mut next);
+            transition.update(&EvaluationFrame::from_rows(current.clone(), next.clone()));
+
+            let cf_flags = transition.cf_op_flags();
+            for (op, count) in self.flow_ops.iter_mut() {
+                if cf_flags[op.op_index()] != BaseElement::ZERO {
+                    *count += 1;
+                }
+            }
+
+            // user operations are executed only when the decoder is in HACC state
+            if cf_flags[FlowOps::Hacc.op_index()] == BaseElement::ZERO {
+                continue;
+            }
+
+            let ld_flags = transition.ld_op_flags();
+            let hd_flags = transition.hd_op_flags();
+            for (op, count) in self.user_ops.iter_mut() {
+                let flag = match op {
+                    UserOps::Begin => transition.begin_flag(),
+                    UserOps::Noop => transition.noop_flag(),
+                    UserOps::Push | UserOps::Cmp | UserOps::RescR => hd_flags[op.hd_index()],
+                    _ => ld_flags[op.ld_index()],
+                };
+                if flag != BaseElement::ZERO {
+                    *count += 1;
+                }
+            }
+        }
+    }
+
+    /// Returns activation counts for all control flow operations.
+    pub fn flow_ops(&self) -> &[(FlowOps, usize)] {
+        &self.flow_ops
+    }
+
+    /// Returns activation counts for all user operations.
+    pub fn user_ops(&self) -> &[(UserOps, usize)] {
+        &self.user_ops
+    }
+
+    /// Returns user operations which were not activated in any of the traces.
+    pub fn unexercised_user_ops(&self) -> Vec<UserOps> {
+        self.user_ops
+            .iter()
+            .filter(|(_, count)| *count == 0)
+            .map(|&(op, _)| op)
+            .collect()
+    }
+}
+
+impl Default for ConstraintCoverage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for ConstraintCoverage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "control flow operations:")?;
+        for (op, count) in self.flow_ops.iter() {
+            writeln!(f, "  {:<8} {}", op.to_string(), count)?;
+        }
+        writeln!(f, "user operations:")?;
+        for (op, count) in self.user_ops.iter() {
+            writeln!(f, "  {:<8} {}", op.to_string(), count)?;
+        }
+        Ok(())
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+impl ProcessorAir {
+    /// Maps a position in the transition constraint evaluation vector to the component the
+    /// constraint belongs to and its index within that component.
+    fn locate_constraint(&self, i: usize) -> (ConstraintComponent, usize) {
+        let aux_start = self.decoder_constraint_count;
+        let stack_start = aux_start + stack::NUM_AUX_CONSTRAINTS;
+        if i < aux_start {
+            (ConstraintComponent::Decoder, i)
+        } else if i < stack_start {
+            (ConstraintComponent::StackAux, i - aux_start)
+        } else {
+            (ConstraintComponent::Stack, i - stack_start)
+        }
+    }
+
+    fn build_state(&self, row: &[BaseElement]) -> TraceState<BaseElement> {
+        TraceState::from_slice(self.ctx_depth, self.loop_depth, self.stack_depth, row)
+    }
+}
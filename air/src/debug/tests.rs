@@ -0,0 +1,33 @@
+use super::{FLOW_OPS, USER_OPS};
+use vm_core::opcodes::UserOps;
+
+#[test]
+fn op_lists() {
+    // every control flow operation is listed exactly once
+    for code in 0..8u8 {
+        let count = FLOW_OPS.iter().filter(|&&op| op as u8 == code).count();
+        assert_eq!(
+            1, count,
+            "control flow op {:#05b} is listed {} times",
+            code, count
+        );
+    }
+
+    // every low-degree operation is listed exactly once; 0b11111 is NOOP
+    for code in 0..32u8 {
+        let code = 0b0_11_00000 | code;
+        let count = USER_OPS.iter().filter(|&&op| op as u8 == code).count();
+        assert_eq!(1, count, "user op {:#010b} is listed {} times", code, count);
+    }
+
+    // every high-degree operation is listed exactly once; 0b11 is NOOP
+    for code in 0..4u8 {
+        let code = (code << 5) | 0b11111;
+        let count = USER_OPS.iter().filter(|&&op| op as u8 == code).count();
+        assert_eq!(1, count, "user op {:#010b} is listed {} times", code, count);
+    }
+
+    // the only other listed operation is the composite BEGIN operation
+    assert!(USER_OPS.contains(&UserOps::Begin));
+    assert_eq!(32 + 3 + 1, USER_OPS.len());
+}
@@ -1,4 +1,4 @@
-use core::convert::{TryFrom, TryInto};
+use core::{convert::TryInto, ops::Range};
 use vm_core::{
     hasher, op_sponge, opcodes, BASE_CYCLE_LENGTH, CF_OP_BITS_RANGE, HD_OP_BITS_RANGE,
     LD_OP_BITS_RANGE, MIN_CONTEXT_DEPTH, MIN_LOOP_DEPTH, NUM_CF_OPS, NUM_HD_OPS, NUM_LD_OPS,
@@ -8,10 +8,11 @@ use winter_air::{
     Air, AirContext, Assertion, EvaluationFrame, ProofOptions as WinterProofOptions, TraceInfo,
     TransitionConstraintDegree,
 };
-use winter_utils::{group_slice_elements, ByteWriter, Serializable};
 
+mod debug;
 mod decoder;
 mod options;
+mod public_inputs;
 mod stack;
 mod transition;
 mod utils;
@@ -19,11 +20,14 @@ mod utils;
 // EXPORTS
 // ================================================================================================
 
+pub use debug::{check_trace, ConstraintComponent, ConstraintCoverage, ConstraintFailure};
 pub use options::ProofOptions;
+pub use public_inputs::{PublicInputs, PublicInputsError};
 pub use transition::VmTransition;
 pub use vm_core::{
-    utils::ToElements, BaseElement, FieldElement, StarkField, TraceState, MAX_OUTPUTS,
-    MIN_TRACE_LENGTH,
+    opcodes::{FlowOps, UserOps},
+    utils::ToElements,
+    BaseElement, FieldElement, StarkField, TraceState, MAX_OUTPUTS, MIN_TRACE_LENGTH,
 };
 pub use winter_air::{FieldExtension, HashFunction};
 
@@ -207,53 +211,21 @@ impl Air for ProcessorAir {
     }
 }
 
-// PUBLIC INPUTS
-// ================================================================================================
-
-pub struct PublicInputs {
-    program_hash: [BaseElement; op_sponge::DIGEST_SIZE],
-    inputs: Vec<BaseElement>,
-    outputs: Vec<BaseElement>,
-}
-
-impl PublicInputs {
-    pub fn new(program_hash: [u8; 32], inputs: &[u128], outputs: &[u128]) -> Self {
-        let program_hash: &[[u8; 16]] = group_slice_elements(&program_hash);
-        let program_hash = [
-            BaseElement::try_from(program_hash[0]).unwrap(),
-            BaseElement::try_from(program_hash[1]).unwrap(),
-        ];
-
-        Self {
-            program_hash,
-            inputs: inputs
-                .iter()
-                .map(|&v| BaseElement::try_from(v).unwrap())
-                .collect(),
-            outputs: outputs
-                .iter()
-                .map(|&v| BaseElement::try_from(v).unwrap())
-                .collect(),
-        }
-    }
-}
-
-impl Serializable for PublicInputs {
-    fn write_into<W: ByteWriter>(&self, target: &mut W) {
-        target.write(&self.program_hash[..]);
-        target.write(&self.inputs);
-        target.write(&self.outputs);
-    }
-}
-
 // TRACE METADATA
 // ================================================================================================
 
+/// Describes the layout of an execution trace generated by the processor.
+///
+/// Registers of the trace are laid out as follows: op counter, op sponge, op bits (cf, ld, hd),
+/// context stack, loop stack, and user stack. The first three groups have fixed widths, while
+/// widths of the stacks depend on the program being executed.
 pub struct TraceMetadata {
     pub op_count: usize,
     pub ctx_depth: usize,
     pub loop_depth: usize,
     pub stack_depth: usize,
+    pub trace_length: usize,
+    pub num_steps: usize,
 }
 
 impl TraceMetadata {
@@ -261,12 +233,80 @@ impl TraceMetadata {
         let op_count = u64::from_le_bytes(trace_info.meta()[..8].try_into().unwrap()) as usize;
         let ctx_depth = trace_info.meta()[8] as usize;
         let loop_depth = trace_info.meta()[9] as usize;
+        let num_steps = u64::from_le_bytes(trace_info.meta()[10..18].try_into().unwrap()) as usize;
         let decoder_width = TraceState::<BaseElement>::compute_decoder_width(ctx_depth, loop_depth);
         TraceMetadata {
             op_count,
             ctx_depth,
             loop_depth,
             stack_depth: trace_info.width() - decoder_width,
+            trace_length: trace_info.length(),
+            num_steps,
         }
     }
+
+    /// Returns the number of registers in the decoder part of the trace (this includes context
+    /// and loop stack registers).
+    pub fn decoder_width(&self) -> usize {
+        TraceState::<BaseElement>::compute_decoder_width(self.ctx_depth, self.loop_depth)
+    }
+
+    /// Returns the total number of registers in the trace.
+    pub fn trace_width(&self) -> usize {
+        self.decoder_width() + self.stack_depth
+    }
+
+    /// Returns indexes of context stack registers.
+    pub fn ctx_stack_range(&self) -> Range<usize> {
+        let start = HD_OP_BITS_RANGE.end;
+        start..(start + self.ctx_depth)
+    }
+
+    /// Returns indexes of loop stack registers.
+    pub fn loop_stack_range(&self) -> Range<usize> {
+        let start = self.ctx_stack_range().end;
+        start..(start + self.loop_depth)
+    }
+
+    /// Returns indexes of user stack registers.
+    pub fn user_stack_range(&self) -> Range<usize> {
+        let start = self.decoder_width();
+        start..(start + self.stack_depth)
+    }
+
+    /// Returns a stable name of the register at the specified index, e.g. `op_sponge_2` or
+    /// `user_stack_0`; the index of a register within its group is appended to the group name.
+    pub fn register_name(&self, index: usize) -> String {
+        if index == OP_COUNTER_IDX {
+            return String::from("op_counter");
+        }
+
+        let groups = [
+            ("op_sponge", OP_SPONGE_RANGE),
+            ("cf_op_bit", CF_OP_BITS_RANGE),
+            ("ld_op_bit", LD_OP_BITS_RANGE),
+            ("hd_op_bit", HD_OP_BITS_RANGE),
+            ("ctx_stack", self.ctx_stack_range()),
+            ("loop_stack", self.loop_stack_range()),
+            ("user_stack", self.user_stack_range()),
+        ];
+
+        for (name, range) in groups.iter() {
+            if range.contains(&index) {
+                return format!("{}_{}", name, index - range.start);
+            }
+        }
+        panic!(
+            "register index {} is out of bounds for a trace of width {}",
+            index,
+            self.trace_width()
+        );
+    }
+
+    /// Returns the number of steps appended to the end of the trace to pad its length to the
+    /// next power of two; values of all registers in these steps are copied from the last
+    /// executed step (op bits are set to encode VOID operations).
+    pub fn padding_length(&self) -> usize {
+        self.trace_length - self.num_steps
+    }
 }
@@ -0,0 +1,269 @@
+use super::{BaseElement, FieldElement, StarkField, MAX_OUTPUTS};
+use core::{convert::TryFrom, fmt};
+use vm_core::{op_sponge, MAX_PUBLIC_INPUTS};
+use winter_utils::{ByteWriter, Serializable};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Version of the byte encoding produced by [PublicInputs::encode()].
+const ENCODING_VERSION: u8 = 1;
+
+/// Number of bytes in an encoded value.
+const VALUE_SIZE: usize = 16;
+
+// PUBLIC INPUTS
+// ================================================================================================
+
+/// Everything a verifier needs to know about a program execution besides the proof: hash of the
+/// executed program, public inputs (initial state of the stack), and outputs (final state of the
+/// stack).
+#[derive(Clone, Debug, PartialEq)]
+pub struct PublicInputs {
+    pub(crate) program_hash: [BaseElement; op_sponge::DIGEST_SIZE],
+    pub(crate) inputs: Vec<BaseElement>,
+    pub(crate) outputs: Vec<BaseElement>,
+}
+
+impl PublicInputs {
+    /// Returns new public inputs instantiated from the specified program hash, inputs, and
+    /// outputs.
+    ///
+    /// # Panics
+    /// Panics if the public inputs are not valid (see [PublicInputs::try_new()]).
+    pub fn new(program_hash: [u8; 32], inputs: &[u128], outputs: &[u128]) -> Self {
+        Self::try_new(program_hash, inputs, outputs)
+            .unwrap_or_else(|err| panic!("invalid public inputs: {}", err))
+    }
+
+    /// Returns new public inputs instantiated from the specified program hash, inputs, and
+    /// outputs.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The number of inputs is greater than [MAX_PUBLIC_INPUTS], or the number of outputs is
+    ///   greater than [MAX_OUTPUTS].
+    /// * Either half of the program hash or any of the values is not a valid field element.
+    pub fn try_new(
+        program_hash: [u8; 32],
+        inputs: &[u128],
+        outputs: &[u128],
+    ) -> Result<Self, PublicInputsError> {
+        if inputs.len() > MAX_PUBLIC_INPUTS {
+            return Err(PublicInputsError::TooManyValues(inputs.len()));
+        }
+        if outputs.len() > MAX_OUTPUTS {
+            return Err(PublicInputsError::TooManyValues(outputs.len()));
+        }
+
+        let program_hash = [
+            decode_value(&program_hash[..VALUE_SIZE])?,
+            decode_value(&program_hash[VALUE_SIZE..])?,
+        ];
+
+        Ok(Self {
+            program_hash,
+            inputs: to_elements(inputs)?,
+            outputs: to_elements(outputs)?,
+        })
+    }
+
+    /// Returns hash of the executed program.
+    pub fn program_hash(&self) -> [u8; 32] {
+        let mut result = [0u8; 32];
+        result.copy_from_slice(BaseElement::elements_as_bytes(&self.program_hash));
+        result
+    }
+
+    /// Returns public inputs against which the program was executed.
+    pub fn inputs(&self) -> Vec<u128> {
+        self.inputs.iter().map(|v| v.as_int()).collect()
+    }
+
+    /// Returns outputs produced by the program.
+    pub fn outputs(&self) -> Vec<u128> {
+        self.outputs.iter().map(|v| v.as_int()).collect()
+    }
+
+    // ENCODING
+    // --------------------------------------------------------------------------------------------
+
+    /// Encodes these public inputs into a stable byte representation.
+    ///
+    /// The encoding starts with a version byte followed by 32 bytes of the program hash, and then
+    /// by inputs and outputs; each of these is encoded as a single byte containing the number of
+    /// values followed by 16-byte little-endian encodings of each value.
+    ///
+    /// Unlike [Serializable] implementation (which is used to seed the public coin of the proof
+    /// system), this encoding can be decoded via [PublicInputs::decode()].
+    pub fn encode(&self) -> Vec<u8> {
+        let num_values = self.inputs.len() + self.outputs.len();
+        let mut result = Vec::with_capacity(35 + num_values * VALUE_SIZE);
+        result.push(ENCODING_VERSION);
+        result.extend_from_slice(&self.program_hash());
+        for values in [&self.inputs, &self.outputs].iter() {
+            result.push(values.len() as u8);
+            for value in values.iter() {
+                result.extend_from_slice(&value.as_int().to_le_bytes());
+            }
+        }
+        result
+    }
+
+    /// Decodes public inputs from bytes produced by [PublicInputs::encode()].
+    ///
+    /// # Errors
+    /// Returns an error if the bytes are not a valid encoding of public inputs.
+    pub fn decode(bytes: &[u8]) -> Result<Self, PublicInputsError> {
+        let (&version, bytes) = bytes
+            .split_first()
+            .ok_or(PublicInputsError::UnexpectedEnd)?;
+        if version != ENCODING_VERSION {
+            return Err(PublicInputsError::UnsupportedVersion(version));
+        }
+
+        if bytes.len() < 2 * VALUE_SIZE {
+            return Err(PublicInputsError::UnexpectedEnd);
+        }
+        let (hash_bytes, bytes) = bytes.split_at(2 * VALUE_SIZE);
+        let program_hash = [
+            decode_value(&hash_bytes[..VALUE_SIZE])?,
+            decode_value(&hash_bytes[VALUE_SIZE..])?,
+        ];
+
+        let (inputs, bytes) = decode_values(bytes, MAX_PUBLIC_INPUTS)?;
+        let (outputs, bytes) = decode_values(bytes, MAX_OUTPUTS)?;
+        if !bytes.is_empty() {
+            return Err(PublicInputsError::TrailingBytes(bytes.len()));
+        }
+
+        Ok(Self {
+            program_hash,
+            inputs,
+            outputs,
+        })
+    }
+}
+
+impl Serializable for PublicInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write(&self.program_hash[..]);
+        target.write(&self.inputs);
+        target.write(&self.outputs);
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for PublicInputs {
+    /// Serializes public inputs as bytes produced by [PublicInputs::encode()].
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&self.encode())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for PublicInputs {
+    /// Deserializes public inputs from bytes produced by [PublicInputs::encode()].
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes = <Vec<u8>>::deserialize(deserializer)?;
+        PublicInputs::decode(&bytes).map_err(serde::de::Error::custom)
+    }
+}
+
+// PUBLIC INPUTS ERROR
+// ================================================================================================
+
+/// Describes why public inputs could not be instantiated or decoded from bytes.
+#[derive(Clone, PartialEq)]
+pub enum PublicInputsError {
+    /// The encoding version is not supported; contains the version.
+    UnsupportedVersion(u8),
+    /// The bytes ended before all values were decoded.
+    UnexpectedEnd,
+    /// The number of inputs or outputs exceeds the allowed maximum; contains the number of
+    /// values.
+    TooManyValues(usize),
+    /// An encoded value (either a half of the program hash, an input, or an output) is not a
+    /// valid field element.
+    InvalidValue(u128),
+    /// There were bytes left after all values were decoded; contains the number of such bytes.
+    TrailingBytes(usize),
+}
+
+impl fmt::Debug for PublicInputsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl fmt::Display for PublicInputsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PublicInputsError::UnsupportedVersion(version) => {
+                write!(f, "encoding version {} is not supported", version)
+            }
+            PublicInputsError::UnexpectedEnd => write!(f, "unexpected end of encoded bytes"),
+            PublicInputsError::TooManyValues(n) => {
+                write!(f, "number of values ({}) is too large", n)
+            }
+            PublicInputsError::InvalidValue(value) => write!(
+                f,
+                "value {} is not a valid field element; values must be smaller than {}",
+                value,
+                BaseElement::MODULUS
+            ),
+            PublicInputsError::TrailingBytes(n) => {
+                write!(f, "{} bytes left after decoding public inputs", n)
+            }
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Decodes a length-prefixed list of values and returns the values together with the remaining
+/// bytes.
+fn decode_values(
+    bytes: &[u8],
+    max_values: usize,
+) -> Result<(Vec<BaseElement>, &[u8]), PublicInputsError> {
+    let (&num_values, bytes) = bytes
+        .split_first()
+        .ok_or(PublicInputsError::UnexpectedEnd)?;
+    let num_values = num_values as usize;
+    if num_values > max_values {
+        return Err(PublicInputsError::TooManyValues(num_values));
+    }
+    if bytes.len() < num_values * VALUE_SIZE {
+        return Err(PublicInputsError::UnexpectedEnd);
+    }
+
+    let (value_bytes, bytes) = bytes.split_at(num_values * VALUE_SIZE);
+    let values = value_bytes
+        .chunks(VALUE_SIZE)
+        .map(decode_value)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok((values, bytes))
+}
+
+/// Converts the specified values into field elements.
+fn to_elements(values: &[u128]) -> Result<Vec<BaseElement>, PublicInputsError> {
+    values.iter().map(|&value| to_element(value)).collect()
+}
+
+/// Converts the specified value into a field element.
+fn to_element(value: u128) -> Result<BaseElement, PublicInputsError> {
+    if value >= BaseElement::MODULUS {
+        return Err(PublicInputsError::InvalidValue(value));
+    }
+    Ok(BaseElement::new(value))
+}
+
+/// Decodes a single field element from its 16-byte little-endian encoding.
+fn decode_value(bytes: &[u8]) -> Result<BaseElement, PublicInputsError> {
+    to_element(u128::from_le_bytes(
+        <[u8; VALUE_SIZE]>::try_from(bytes).unwrap(),
+    ))
+}
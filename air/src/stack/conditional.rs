@@ -98,3 +98,68 @@ pub fn enforce_cswap2<E>(
     // make sure the condition was a binary value
     aux.agg_constraint(0, op_flag, is_binary(condition));
 }
+
+/// Enforces constraints for CSWAP operation. These constraints work with top 3 registers of the
+/// stack and enforce that when condition = 1, v0 and v1 are swapped; when condition = 0, v0 and
+/// v1 remain in place. In both cases, the condition is removed from the stack.
+pub fn enforce_cswap<E>(
+    result: &mut [E],
+    aux: &mut [E],
+    old_stack: &[E],
+    new_stack: &[E],
+    op_flag: E,
+) where
+    E: FieldElement,
+{
+    let condition = old_stack[0];
+    let v0 = old_stack[1];
+    let v1 = old_stack[2];
+
+    let not_condition = binary_not(condition);
+    let op_result0 = condition * v1 + not_condition * v0;
+    let op_result1 = condition * v0 + not_condition * v1;
+    result.agg_constraint(0, op_flag, are_equal(new_stack[0], op_result0));
+    result.agg_constraint(1, op_flag, are_equal(new_stack[1], op_result1));
+
+    // registers beyond the 3rd are shifted left by 1 slot
+    enforce_left_shift(result, old_stack, new_stack, 3, 1, op_flag);
+
+    // make sure the condition was a binary value
+    aux.agg_constraint(0, op_flag, is_binary(condition));
+}
+
+/// Enforces constraints for CSWAPW operation. These constraints work with top 5 registers of the
+/// stack and enforce that when condition = 1, (v0, v1) and (v2, v3) are swapped; when
+/// condition = 0, the words remain in place. In both cases, the condition is removed from the
+/// stack.
+pub fn enforce_cswapw<E>(
+    result: &mut [E],
+    aux: &mut [E],
+    old_stack: &[E],
+    new_stack: &[E],
+    op_flag: E,
+) where
+    E: FieldElement,
+{
+    let condition = old_stack[0];
+    let v0 = old_stack[1];
+    let v1 = old_stack[2];
+    let v2 = old_stack[3];
+    let v3 = old_stack[4];
+
+    let not_condition = binary_not(condition);
+    let op_result0 = condition * v2 + not_condition * v0;
+    let op_result1 = condition * v3 + not_condition * v1;
+    let op_result2 = condition * v0 + not_condition * v2;
+    let op_result3 = condition * v1 + not_condition * v3;
+    result.agg_constraint(0, op_flag, are_equal(new_stack[0], op_result0));
+    result.agg_constraint(1, op_flag, are_equal(new_stack[1], op_result1));
+    result.agg_constraint(2, op_flag, are_equal(new_stack[2], op_result2));
+    result.agg_constraint(3, op_flag, are_equal(new_stack[3], op_result3));
+
+    // registers beyond the 5th are shifted left by 1 slot
+    enforce_left_shift(result, old_stack, new_stack, 5, 1, op_flag);
+
+    // make sure the condition was a binary value
+    aux.agg_constraint(0, op_flag, is_binary(condition));
+}
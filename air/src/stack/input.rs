@@ -1,4 +1,4 @@
-use super::{enforce_right_shift, FieldElement};
+use super::{are_equal, enforce_right_shift, EvaluationResult, FieldElement};
 
 /// Enforces constraints for PUSH operation. The constraints on the first element of the stack
 /// are enforced in the Decoder where the value pushed onto the stack is injected into sponge
@@ -27,3 +27,24 @@ where
 {
     enforce_right_shift(result, old_stack, new_stack, 2, op_flag);
 }
+
+/// Enforces constraints for SDEPTH operation. The execution trace does not record the depth of
+/// the stack, and thus, no constraints are placed on the first element of the stack; the old
+/// stack is shifted right by 1 element.
+pub fn enforce_sdepth<E>(result: &mut [E], old_stack: &[E], new_stack: &[E], op_flag: E)
+where
+    E: FieldElement,
+{
+    enforce_right_shift(result, old_stack, new_stack, 1, op_flag);
+}
+
+/// Enforces constraints for CLK operation. The first element of the stack must be equal to the
+/// value of the op counter after the operation is executed; the old stack is shifted right by
+/// 1 element.
+pub fn enforce_clk<E>(result: &mut [E], old_stack: &[E], new_stack: &[E], op_counter: E, op_flag: E)
+where
+    E: FieldElement,
+{
+    result.agg_constraint(0, op_flag, are_equal(new_stack[0], op_counter));
+    enforce_right_shift(result, old_stack, new_stack, 1, op_flag);
+}
@@ -9,7 +9,7 @@ use crate::{
 };
 
 mod input;
-use input::{enforce_push, enforce_read, enforce_read2};
+use input::{enforce_clk, enforce_push, enforce_read, enforce_read2, enforce_sdepth};
 
 mod arithmetic;
 use arithmetic::{
@@ -26,7 +26,7 @@ mod comparison;
 use comparison::{enforce_assert, enforce_asserteq, enforce_binacc, enforce_cmp, enforce_eq};
 
 mod conditional;
-use conditional::{enforce_choose, enforce_choose2, enforce_cswap2};
+use conditional::{enforce_choose, enforce_choose2, enforce_cswap, enforce_cswap2, enforce_cswapw};
 
 mod hash;
 use hash::enforce_rescr;
@@ -95,6 +95,19 @@ pub fn enforce_constraints<E: FieldElement<BaseField = BaseElement>>(
         new_stack,
         ld_flags[OpCode::Read2.ld_index()],
     );
+    enforce_clk(
+        &mut evaluations,
+        old_stack,
+        new_stack,
+        transition.next().op_counter(),
+        ld_flags[OpCode::Clk.ld_index()],
+    );
+    enforce_sdepth(
+        &mut evaluations,
+        old_stack,
+        new_stack,
+        ld_flags[OpCode::SDepth.ld_index()],
+    );
 
     // stack manipulation operations
     enforce_dup(
@@ -252,6 +265,20 @@ pub fn enforce_constraints<E: FieldElement<BaseField = BaseElement>>(
         new_stack,
         ld_flags[OpCode::CSwap2.ld_index()],
     );
+    enforce_cswap(
+        &mut evaluations,
+        aux,
+        old_stack,
+        new_stack,
+        ld_flags[OpCode::CSwap.ld_index()],
+    );
+    enforce_cswapw(
+        &mut evaluations,
+        aux,
+        old_stack,
+        new_stack,
+        ld_flags[OpCode::CSwapW.ld_index()],
+    );
 
     // 2 ----- enforce constraints for high-degree operations --------------------------------------
     let hd_flags = transition.hd_op_flags();
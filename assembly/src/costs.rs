@@ -0,0 +1,90 @@
+use super::{
+    instructions::{Instruction, INSTRUCTION_SET},
+    HintMap, OpCode,
+};
+use core::fmt::Write;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of distinct positions of an instruction relative to operation alignment boundaries;
+/// this is the largest alignment required by any operation (hashing starts on multiples of 16).
+const NUM_ALIGNMENTS: usize = 16;
+
+// INSTRUCTION COSTS
+// ================================================================================================
+
+/// Number of VM cycles consumed by a single form of an assembly instruction.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InstructionCost {
+    /// Instruction together with its parameters (e.g. `dup.2`).
+    pub instruction: String,
+    /// Number of cycles consumed when the instruction requires the least alignment padding.
+    pub min_cycles: usize,
+    /// Number of cycles consumed when the instruction requires the most alignment padding.
+    pub max_cycles: usize,
+}
+
+/// Returns the number of VM cycles consumed by every valid form of every assembly instruction.
+///
+/// Costs are computed by compiling each instruction form at every position relative to operation
+/// alignment boundaries, and thus, always match the code generated by the assembler. Some
+/// instructions (e.g. `push` or `hash`) pad the program with NOOPs to align their operations;
+/// the number of padding NOOPs depends on where the instruction is located in the program, and
+/// thus, the cost of such instructions is reported as a range.
+///
+/// The table is built at runtime rather than provided as a `const`: instruction parsers are
+/// regular functions which cannot be evaluated in a const context, and a hand-maintained copy
+/// of their output could drift from the assembler. Use `instruction_costs_json()` to export the
+/// table as static data. The VM has no auxiliary components, so cycles are the only cost of an
+/// instruction.
+pub fn instruction_costs() -> Vec<InstructionCost> {
+    let mut result = Vec::new();
+    for instruction in INSTRUCTION_SET.iter() {
+        for form in instruction.forms() {
+            let cycles = (0..NUM_ALIGNMENTS)
+                .map(|offset| compute_cycles(instruction, &form, offset))
+                .collect::<Vec<_>>();
+            result.push(InstructionCost {
+                min_cycles: *cycles.iter().min().unwrap(),
+                max_cycles: *cycles.iter().max().unwrap(),
+                instruction: form,
+            });
+        }
+    }
+    result
+}
+
+/// Returns the table produced by `instruction_costs()` serialized as a JSON array; each entry is
+/// an object with `instruction`, `min_cycles`, and `max_cycles` fields.
+pub fn instruction_costs_json() -> String {
+    let mut json = String::from("[");
+    for (i, cost) in instruction_costs().iter().enumerate() {
+        if i > 0 {
+            json.push(',');
+        }
+        write!(
+            json,
+            "\n  {{\"instruction\": \"{}\", \"min_cycles\": {}, \"max_cycles\": {}}}",
+            cost.instruction, cost.min_cycles, cost.max_cycles
+        )
+        .unwrap();
+    }
+    json.push_str("\n]\n");
+    json
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the number of operations the assembler emits for the specified form of an instruction
+/// when the instruction is preceded by `offset` operations.
+fn compute_cycles(instruction: &Instruction, form: &str, offset: usize) -> usize {
+    let op: Vec<&str> = form.split('.').collect();
+    let mut op_codes = vec![OpCode::Noop; offset];
+    let mut op_hints = HintMap::new();
+    instruction
+        .parse(&mut op_codes, &mut op_hints, &op, 0)
+        .unwrap_or_else(|err| panic!("instruction {} failed to compile: {}", form, err));
+    op_codes.len() - offset
+}
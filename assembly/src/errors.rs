@@ -1,12 +1,70 @@
+use crate::instructions::{BLOCK_KEYWORDS, INSTRUCTION_SET};
 use core::fmt;
 
 // ASSEMBLY ERROR
 // ================================================================================================
 
 pub struct AssemblyError {
+    kind: AssemblyErrorKind,
     message: String,
     step: usize,
     op: String,
+    suggestion: Option<String>,
+}
+
+/// Identifies the type of an [AssemblyError]; every kind has a unique error code.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum AssemblyErrorKind {
+    EmptyProgram,
+    EmptyBlock,
+    InvalidProgramStart,
+    InvalidProgramEnd,
+    DanglingInstructions,
+    InvalidOp,
+    MissingParam,
+    ExtraParam,
+    InvalidParam,
+    InvalidBlockHead,
+    InvalidNumIterations,
+    DanglingElse,
+    UnmatchedBlock,
+    UnmatchedIf,
+    UnmatchedWhile,
+    UnmatchedRepeat,
+    UnmatchedElse,
+    BlockDepthExceeded,
+    LoopDepthExceeded,
+    SpanTooLong,
+}
+
+impl AssemblyErrorKind {
+    /// Returns a stable machine-readable code of this error kind (e.g. "E0006").
+    ///
+    /// Codes are never reassigned: new error kinds always receive new codes.
+    pub fn code(&self) -> &'static str {
+        match self {
+            AssemblyErrorKind::EmptyProgram => "E0001",
+            AssemblyErrorKind::EmptyBlock => "E0002",
+            AssemblyErrorKind::InvalidProgramStart => "E0003",
+            AssemblyErrorKind::InvalidProgramEnd => "E0004",
+            AssemblyErrorKind::DanglingInstructions => "E0005",
+            AssemblyErrorKind::InvalidOp => "E0006",
+            AssemblyErrorKind::MissingParam => "E0007",
+            AssemblyErrorKind::ExtraParam => "E0008",
+            AssemblyErrorKind::InvalidParam => "E0009",
+            AssemblyErrorKind::InvalidBlockHead => "E0010",
+            AssemblyErrorKind::InvalidNumIterations => "E0011",
+            AssemblyErrorKind::DanglingElse => "E0012",
+            AssemblyErrorKind::UnmatchedBlock => "E0013",
+            AssemblyErrorKind::UnmatchedIf => "E0014",
+            AssemblyErrorKind::UnmatchedWhile => "E0015",
+            AssemblyErrorKind::UnmatchedRepeat => "E0016",
+            AssemblyErrorKind::UnmatchedElse => "E0017",
+            AssemblyErrorKind::BlockDepthExceeded => "E0018",
+            AssemblyErrorKind::LoopDepthExceeded => "E0019",
+            AssemblyErrorKind::SpanTooLong => "E0020",
+        }
+    }
 }
 
 impl AssemblyError {
@@ -15,159 +73,257 @@ impl AssemblyError {
 
     pub fn empty_program() -> AssemblyError {
         AssemblyError {
+            kind: AssemblyErrorKind::EmptyProgram,
             message: String::from("a program must contain at least one instruction"),
             step: 0,
             op: String::from("begin"),
+            suggestion: None,
         }
     }
 
     pub fn empty_block(op: &[&str], step: usize) -> AssemblyError {
         AssemblyError {
+            kind: AssemblyErrorKind::EmptyBlock,
             message: String::from("a program block must contain at least one instruction"),
             step,
             op: op.join("."),
+            suggestion: None,
         }
     }
 
     pub fn invalid_program_start(op: &str) -> AssemblyError {
         AssemblyError {
+            kind: AssemblyErrorKind::InvalidProgramStart,
             message: String::from("a program must start with a 'being' instruction"),
             step: 0,
             op: String::from(op),
+            suggestion: None,
         }
     }
 
     pub fn invalid_program_end(op: &str) -> AssemblyError {
         AssemblyError {
+            kind: AssemblyErrorKind::InvalidProgramEnd,
             message: String::from("a program must end with an 'end' instruction"),
             step: 0,
             op: String::from(op),
+            suggestion: None,
         }
     }
 
     pub fn dangling_instructions(step: usize) -> AssemblyError {
         AssemblyError {
+            kind: AssemblyErrorKind::DanglingInstructions,
             message: "dangling instructions after program end".to_string(),
             step,
             op: String::from("end"),
+            suggestion: None,
         }
     }
 
     pub fn invalid_op(op: &[&str], step: usize) -> AssemblyError {
+        let suggestion = suggest_instruction(op[0]);
+        let message = match suggestion {
+            Some(name) => format!(
+                "instruction {} is invalid; did you mean `{}`?",
+                op.join("."),
+                name
+            ),
+            None => format!("instruction {} is invalid", op.join(".")),
+        };
         AssemblyError {
-            message: format!("instruction {} is invalid", op.join(".")),
+            kind: AssemblyErrorKind::InvalidOp,
+            message,
             step,
             op: op.join("."),
+            suggestion: suggestion.map(String::from),
         }
     }
 
     pub fn missing_param(op: &[&str], step: usize) -> AssemblyError {
         AssemblyError {
+            kind: AssemblyErrorKind::MissingParam,
             message: format!("malformed instruction {}: parameter is missing", op[0]),
             step,
             op: op.join("."),
+            suggestion: None,
         }
     }
 
     pub fn extra_param(op: &[&str], step: usize) -> AssemblyError {
         AssemblyError {
+            kind: AssemblyErrorKind::ExtraParam,
             message: format!(
                 "malformed instruction {}: too many parameters provided",
                 op[0]
             ),
             step,
             op: op.join("."),
+            suggestion: None,
         }
     }
 
     pub fn invalid_param(op: &[&str], step: usize) -> AssemblyError {
         AssemblyError {
+            kind: AssemblyErrorKind::InvalidParam,
             message: format!(
                 "malformed instruction {}: parameter '{}' is invalid",
                 op[0], op[1]
             ),
             step,
             op: op.join("."),
+            suggestion: None,
         }
     }
 
     pub fn invalid_param_reason(op: &[&str], step: usize, reason: String) -> AssemblyError {
         AssemblyError {
+            kind: AssemblyErrorKind::InvalidParam,
             message: format!("malformed instruction {}: {}", op[0], reason),
             step,
             op: op.join("."),
+            suggestion: None,
         }
     }
 
     pub fn invalid_block_head(op: &[&str], step: usize) -> AssemblyError {
         AssemblyError {
+            kind: AssemblyErrorKind::InvalidBlockHead,
             message: format!("invalid block head '{}'", op.join(".")),
             step,
             op: op.join("."),
+            suggestion: None,
         }
     }
 
     pub fn invalid_num_iterations(op: &[&str], step: usize) -> AssemblyError {
         AssemblyError {
+            kind: AssemblyErrorKind::InvalidNumIterations,
             message: format!(
                 "invalid repeat statement '{}': 2 or more iterations must be specified",
                 op.join(".")
             ),
             step,
             op: op.join("."),
+            suggestion: None,
         }
     }
 
     pub fn dangling_else(step: usize) -> AssemblyError {
         AssemblyError {
+            kind: AssemblyErrorKind::DanglingElse,
             message: "else without matching if".to_string(),
             step,
             op: String::from("else"),
+            suggestion: None,
         }
     }
 
     pub fn unmatched_block(step: usize) -> AssemblyError {
         AssemblyError {
+            kind: AssemblyErrorKind::UnmatchedBlock,
             message: "block without matching end".to_string(),
             step,
             op: String::from("block"),
+            suggestion: None,
         }
     }
 
     pub fn unmatched_if(step: usize) -> AssemblyError {
         AssemblyError {
+            kind: AssemblyErrorKind::UnmatchedIf,
             message: "if without matching else/end".to_string(),
             step,
             op: String::from("if.true"),
+            suggestion: None,
         }
     }
 
     pub fn unmatched_while(step: usize) -> AssemblyError {
         AssemblyError {
+            kind: AssemblyErrorKind::UnmatchedWhile,
             message: "while without matching end".to_string(),
             step,
             op: String::from("while.true"),
+            suggestion: None,
         }
     }
 
     pub fn unmatched_repeat(step: usize, op: &[&str]) -> AssemblyError {
         AssemblyError {
+            kind: AssemblyErrorKind::UnmatchedRepeat,
             message: "repeat without matching end".to_string(),
             step,
             op: op.join("."),
+            suggestion: None,
         }
     }
 
     pub fn unmatched_else(step: usize) -> AssemblyError {
         AssemblyError {
+            kind: AssemblyErrorKind::UnmatchedElse,
             message: "else without matching end".to_string(),
             step,
             op: String::from("else"),
+            suggestion: None,
+        }
+    }
+
+    pub fn block_depth_exceeded(op: &[&str], step: usize, max_depth: usize) -> AssemblyError {
+        AssemblyError {
+            kind: AssemblyErrorKind::BlockDepthExceeded,
+            message: format!(
+                "block '{}' exceeds maximum block nesting depth of {}",
+                op.join("."),
+                max_depth
+            ),
+            step,
+            op: op.join("."),
+            suggestion: None,
+        }
+    }
+
+    pub fn loop_depth_exceeded(op: &[&str], step: usize, max_depth: usize) -> AssemblyError {
+        AssemblyError {
+            kind: AssemblyErrorKind::LoopDepthExceeded,
+            message: format!(
+                "loop '{}' exceeds maximum loop nesting depth of {}",
+                op.join("."),
+                max_depth
+            ),
+            step,
+            op: op.join("."),
+            suggestion: None,
+        }
+    }
+
+    pub fn span_too_long(op: &str, step: usize, max_length: usize) -> AssemblyError {
+        AssemblyError {
+            kind: AssemblyErrorKind::SpanTooLong,
+            message: format!(
+                "instruction {} exceeds maximum span length of {} operations",
+                op, max_length
+            ),
+            step,
+            op: String::from(op),
+            suggestion: None,
         }
     }
 
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
+
+    /// Returns the kind of this error; unlike the message, the kind is not affected by changes
+    /// to error descriptions, and thus, can be used to match on specific errors.
+    pub fn kind(&self) -> AssemblyErrorKind {
+        self.kind
+    }
+
+    /// Returns a stable machine-readable code of this error; see [AssemblyErrorKind::code()].
+    pub fn code(&self) -> &'static str {
+        self.kind.code()
+    }
+
     pub fn message(&self) -> &String {
         &self.message
     }
@@ -179,6 +335,12 @@ impl AssemblyError {
     pub fn step(&self) -> usize {
         self.step
     }
+
+    /// Returns the name of a valid instruction which is the closest match to an invalid
+    /// instruction, if such an instruction exists.
+    pub fn suggestion(&self) -> Option<&str> {
+        self.suggestion.as_deref()
+    }
 }
 
 // COMMON TRAIT IMPLEMENTATIONS
@@ -186,12 +348,58 @@ impl AssemblyError {
 
 impl fmt::Debug for AssemblyError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "assembly error at {}: {}", self.step, self.message)
+        write!(
+            f,
+            "assembly error {} at {}: {}",
+            self.code(),
+            self.step,
+            self.message
+        )
     }
 }
 
 impl fmt::Display for AssemblyError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "assembly error at {}: {}", self.step, self.message)
+        write!(
+            f,
+            "assembly error {} at {}: {}",
+            self.code(),
+            self.step,
+            self.message
+        )
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the instruction with the smallest edit distance to the specified name, as long as
+/// the distance is small enough for the name to be a plausible typo of the instruction.
+fn suggest_instruction(name: &str) -> Option<&'static str> {
+    let max_distance = core::cmp::max(1, name.len() / 3);
+    let instructions = INSTRUCTION_SET.iter().map(|instruction| instruction.name());
+    BLOCK_KEYWORDS
+        .iter()
+        .copied()
+        .chain(instructions)
+        .map(|instruction| (instruction, edit_distance(name, instruction)))
+        .filter(|&(_, distance)| distance <= max_distance)
+        .min_by_key(|&(_, distance)| distance)
+        .map(|(instruction, _)| instruction)
+}
+
+/// Computes Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = diagonal + (ca != cb) as usize;
+            diagonal = row[j + 1];
+            row[j + 1] = core::cmp::min(substitution, core::cmp::min(row[j], row[j + 1]) + 1);
+        }
     }
+    row[b.len()]
 }
@@ -0,0 +1,167 @@
+use super::{parsers::*, AssemblyError, HintMap, OpCode};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Keywords which start or end program blocks; these are not instructions, but are used to
+/// suggest corrections for invalid instructions.
+pub const BLOCK_KEYWORDS: [&str; 7] = ["begin", "block", "if", "else", "while", "repeat", "end"];
+
+/// All instructions recognized by the assembler.
+///
+/// This is the only place where instructions are registered: the assembler parses instructions,
+/// suggests corrections for invalid instructions, and computes instruction costs based on this
+/// table.
+pub static INSTRUCTION_SET: [Instruction; 41] = [
+    Instruction::new("noop", Parser::Ops(parse_noop), Params::NONE),
+    Instruction::new(
+        "assert",
+        Parser::Ops(parse_assert),
+        Params::List(&["", "eq"]),
+    ),
+    Instruction::new(
+        "push",
+        Parser::OpsWithHints(parse_push),
+        Params::List(&["0", "0.0", "0.0.0", "0.0.0.0"]),
+    ),
+    Instruction::new("read", Parser::Ops(parse_read), Params::List(&["", "ab"])),
+    Instruction::new(
+        "assert_stack_top",
+        Parser::OpsWithHints(parse_assert_stack_top),
+        Params::List(&["0", "0.0", "0.0.0", "0.0.0.0"]),
+    ),
+    Instruction::new("clk", Parser::Ops(parse_clk), Params::NONE),
+    Instruction::new("sdepth", Parser::Ops(parse_sdepth), Params::NONE),
+    Instruction::new("dup", Parser::Ops(parse_dup), Params::Range(1, 4)),
+    Instruction::new("pad", Parser::Ops(parse_pad), Params::Range(1, 8)),
+    Instruction::new("pick", Parser::Ops(parse_pick), Params::Range(1, 3)),
+    Instruction::new("drop", Parser::Ops(parse_drop), Params::Range(1, 8)),
+    Instruction::new(
+        "swap",
+        Parser::Ops(parse_swap),
+        Params::List(&["1", "2", "4"]),
+    ),
+    Instruction::new("roll", Parser::Ops(parse_roll), Params::List(&["4", "8"])),
+    Instruction::new("movupw", Parser::Ops(parse_movupw), Params::Range(2, 3)),
+    Instruction::new("movdnw", Parser::Ops(parse_movdnw), Params::Range(2, 3)),
+    Instruction::new("swapdw", Parser::Ops(parse_swapdw), Params::NONE),
+    Instruction::new("add", Parser::Ops(parse_add), Params::NONE),
+    Instruction::new("sub", Parser::Ops(parse_sub), Params::NONE),
+    Instruction::new("mul", Parser::Ops(parse_mul), Params::NONE),
+    Instruction::new("div", Parser::Ops(parse_div), Params::NONE),
+    Instruction::new("neg", Parser::Ops(parse_neg), Params::NONE),
+    Instruction::new("inv", Parser::Ops(parse_inv), Params::NONE),
+    Instruction::new("not", Parser::Ops(parse_not), Params::NONE),
+    Instruction::new("and", Parser::Ops(parse_and), Params::NONE),
+    Instruction::new("or", Parser::Ops(parse_or), Params::NONE),
+    Instruction::new("eq", Parser::OpsWithHints(parse_eq), Params::NONE),
+    Instruction::new("ne", Parser::OpsWithHints(parse_ne), Params::NONE),
+    Instruction::new("gt", Parser::OpsWithHints(parse_gt), Params::Range(4, 128)),
+    Instruction::new("lt", Parser::OpsWithHints(parse_lt), Params::Range(4, 128)),
+    Instruction::new("rc", Parser::OpsWithHints(parse_rc), Params::Range(4, 128)),
+    Instruction::new(
+        "isodd",
+        Parser::OpsWithHints(parse_isodd),
+        Params::Range(4, 128),
+    ),
+    Instruction::new("choose", Parser::Ops(parse_choose), Params::Range(1, 2)),
+    Instruction::new("cswap", Parser::Ops(parse_cswap), Params::NONE),
+    Instruction::new("cswapw", Parser::Ops(parse_cswapw), Params::NONE),
+    Instruction::new("cdrop", Parser::Ops(parse_cdrop), Params::NONE),
+    Instruction::new("cdropw", Parser::Ops(parse_cdropw), Params::NONE),
+    Instruction::new("hash", Parser::Ops(parse_hash), Params::Range(1, 4)),
+    Instruction::new("hmerge", Parser::Ops(parse_hmerge), Params::NONE),
+    Instruction::new("hperm", Parser::Ops(parse_hperm), Params::NONE),
+    Instruction::new("smpath", Parser::Ops(parse_smpath), Params::Range(2, 256)),
+    Instruction::new(
+        "pmpath",
+        Parser::OpsWithHints(parse_pmpath),
+        Params::Range(2, 256),
+    ),
+];
+
+// INSTRUCTION
+// ================================================================================================
+
+/// An assembly instruction together with the parser which translates it into VM operations.
+pub struct Instruction {
+    name: &'static str,
+    parser: Parser,
+    params: Params,
+}
+
+/// Parser of an instruction; parsers of instructions which need to provide execution hints for
+/// the emitted operations also receive the hint map.
+pub enum Parser {
+    Ops(fn(&mut Vec<OpCode>, &[&str], usize) -> Result<(), AssemblyError>),
+    OpsWithHints(fn(&mut Vec<OpCode>, &mut HintMap, &[&str], usize) -> Result<(), AssemblyError>),
+}
+
+/// Parameters accepted by an instruction; an empty string stands for an instruction without
+/// parameters, and ranges are inclusive.
+pub enum Params {
+    List(&'static [&'static str]),
+    Range(u32, u32),
+}
+
+impl Params {
+    /// Parameters of instructions which accept no parameters.
+    const NONE: Params = Params::List(&[""]);
+}
+
+impl Instruction {
+    const fn new(name: &'static str, parser: Parser, params: Params) -> Self {
+        Instruction {
+            name,
+            parser,
+            params,
+        }
+    }
+
+    /// Returns the name of this instruction (i.e. the instruction without parameters).
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the parameters accepted by this instruction.
+    pub fn params(&self) -> &Params {
+        &self.params
+    }
+
+    /// Returns every valid form of this instruction (e.g. `dup.1` through `dup.4`).
+    pub fn forms(&self) -> Vec<String> {
+        match self.params {
+            Params::List(params) => params
+                .iter()
+                .map(|param| match *param {
+                    "" => self.name.to_string(),
+                    param => format!("{}.{}", self.name, param),
+                })
+                .collect(),
+            Params::Range(min, max) => (min..=max)
+                .map(|n| format!("{}.{}", self.name, n))
+                .collect(),
+        }
+    }
+
+    /// Appends operations for the specified instance of this instruction to the program.
+    pub fn parse(
+        &self,
+        op_codes: &mut Vec<OpCode>,
+        op_hints: &mut HintMap,
+        op: &[&str],
+        step: usize,
+    ) -> Result<(), AssemblyError> {
+        match self.parser {
+            Parser::Ops(parser) => parser(op_codes, op, step),
+            Parser::OpsWithHints(parser) => parser(op_codes, op_hints, op, step),
+        }
+    }
+}
+
+/// Returns the instruction with the specified name, or None if there is no such instruction.
+pub fn get_instruction(name: &str) -> Option<&'static Instruction> {
+    INSTRUCTION_SET
+        .iter()
+        .find(|instruction| instruction.name == name)
+}
@@ -2,17 +2,26 @@ use vm_core::{
     opcodes::{OpHint, UserOps as OpCode},
     program::{
         blocks::{Group, Loop, ProgramBlock, Span, Switch},
-        Program,
+        Program, ProgramSignature,
     },
-    BaseElement, FieldElement, StarkField, BASE_CYCLE_LENGTH,
+    BaseElement, FieldElement, StarkField, BASE_CYCLE_LENGTH, MAX_OUTPUTS, MAX_PUBLIC_INPUTS,
 };
 use winter_utils::collections::BTreeMap;
 
 mod parsers;
 use parsers::*;
 
+mod instructions;
+use instructions::get_instruction;
+
 mod errors;
-pub use errors::AssemblyError;
+pub use errors::{AssemblyError, AssemblyErrorKind};
+
+mod options;
+pub use options::AssemblyOptions;
+
+mod costs;
+pub use costs::{instruction_costs, instruction_costs_json, InstructionCost};
 
 #[cfg(test)]
 mod tests;
@@ -25,23 +34,38 @@ type HintMap = BTreeMap<usize, OpHint>;
 // ASSEMBLY COMPILER
 // ================================================================================================
 
-/// Compiles provided assembly code into a program.
+/// Compiles provided assembly code into a program using default [AssemblyOptions].
 pub fn compile(source: &str) -> Result<Program, AssemblyError> {
+    compile_with_options(source, &AssemblyOptions::default())
+}
+
+/// Compiles provided assembly code into a program; compilation fails if the program exceeds
+/// limits specified by the `options`.
+pub fn compile_with_options(
+    source: &str,
+    options: &AssemblyOptions,
+) -> Result<Program, AssemblyError> {
     // break assembly string into tokens
     let tokens: Vec<&str> = source.split_whitespace().collect();
 
     // perform basic validation
     if tokens.is_empty() {
         return Err(AssemblyError::empty_program());
-    } else if tokens[0] != "begin" {
+    }
+    let head: Vec<&str> = tokens[0].split('.').collect();
+    if head[0] != "begin" {
         return Err(AssemblyError::invalid_program_start(tokens[0]));
     } else if tokens[tokens.len() - 1] != "end" {
         return Err(AssemblyError::invalid_program_end(tokens[tokens.len() - 1]));
     }
 
+    // read inputs and outputs declared in the program head
+    let signature = parse_signature(&head)?;
+
     // read the program from the token stream
     let mut root_blocks = Vec::new();
-    let i = parse_branch(&mut root_blocks, &tokens, 0)?;
+    let mut ctx = ParserContext::new(options);
+    let i = parse_branch(&mut root_blocks, &tokens, 0, &mut ctx)?;
     let root = Group::new(root_blocks);
 
     // make sure there is nothing left after the last token
@@ -50,7 +74,7 @@ pub fn compile(source: &str) -> Result<Program, AssemblyError> {
     }
 
     // build and return the program
-    Ok(Program::new(root))
+    Ok(Program::new(root).with_signature(signature))
 }
 
 // PARSER FUNCTIONS
@@ -62,12 +86,17 @@ fn parse_block(
     parent: &mut Vec<ProgramBlock>,
     tokens: &[&str],
     mut i: usize,
+    ctx: &mut ParserContext,
 ) -> Result<usize, AssemblyError> {
     // read the block header
     let head: Vec<&str> = tokens[i].split('.').collect();
 
+    // make sure the block does not exceed nesting limits
+    let is_loop = head[0] == "while";
+    ctx.enter_block(&head, i, is_loop)?;
+
     // based on the block header, figure out what type of a block we are dealing with
-    match head[0] {
+    let result = match head[0] {
         "block" => {
             // make sure block head instruction is valid
             if head.len() > 1 {
@@ -75,7 +104,7 @@ fn parse_block(
             }
             // then parse the body of the block, add the new block to the parent, and return
             let mut body = Vec::new();
-            i = parse_branch(&mut body, tokens, i)?;
+            i = parse_branch(&mut body, tokens, i, ctx)?;
             parent.push(Group::new_block(body));
             Ok(i + 1)
         }
@@ -87,13 +116,13 @@ fn parse_block(
 
             // parse the body of the true branch
             let mut t_branch = Vec::new();
-            i = parse_branch(&mut t_branch, tokens, i)?;
+            i = parse_branch(&mut t_branch, tokens, i, ctx)?;
 
             // if the false branch is present, parse it as well; otherwise
             // create an empty false branch
             let mut f_branch = Vec::new();
             if tokens[i] == "else" {
-                i = parse_branch(&mut f_branch, tokens, i)?;
+                i = parse_branch(&mut f_branch, tokens, i, ctx)?;
             } else {
                 f_branch.push(Span::new_block(vec![
                     OpCode::Not,
@@ -127,7 +156,7 @@ fn parse_block(
 
             // parse loop body
             let mut body_template = Vec::new();
-            i = parse_branch(&mut body_template, tokens, i)?;
+            i = parse_branch(&mut body_template, tokens, i, ctx)?;
 
             // duplicate loop body as many times as needed
             let body = repeat_block_sequence(body_template, num_iterations);
@@ -143,12 +172,15 @@ fn parse_block(
             }
             // then parse the body of the block, add the new block to the parent, and return
             let mut body = Vec::new();
-            i = parse_branch(&mut body, tokens, i)?;
+            i = parse_branch(&mut body, tokens, i, ctx)?;
             parent.push(Loop::new_block(body));
             Ok(i + 1)
         }
         _ => Err(AssemblyError::invalid_block_head(&head, i)),
-    }
+    };
+
+    ctx.exit_block(is_loop);
+    result
 }
 
 /// Builds a body of a program block by parsing tokens from the stream and transforming
@@ -157,6 +189,7 @@ fn parse_branch(
     body: &mut Vec<ProgramBlock>,
     tokens: &[&str],
     mut i: usize,
+    ctx: &mut ParserContext,
 ) -> Result<usize, AssemblyError> {
     // determine starting instructions of the branch based on branch head
     let mut head: Vec<&str> = tokens[i].split('.').collect();
@@ -175,6 +208,10 @@ fn parse_branch(
     };
     let mut op_hints: HintMap = BTreeMap::new();
 
+    // number of instructions parsed since the last block boundary; operations added for the
+    // branch head and for alignment do not count towards the span length limit
+    let mut span_length = 0;
+
     // save first step to check for empty branches
     let first_step = i;
     i += 1;
@@ -187,7 +224,8 @@ fn parse_branch(
             "block" | "if" | "repeat" | "while" => {
                 let force_span = body.is_empty();
                 add_span(body, &mut op_codes, &mut op_hints, force_span);
-                parse_block(body, tokens, i)?
+                span_length = 0;
+                parse_block(body, tokens, i, ctx)?
             }
             "else" => {
                 if head[0] != "if" {
@@ -205,7 +243,18 @@ fn parse_branch(
                 add_span(body, &mut op_codes, &mut op_hints, false);
                 return Ok(i);
             }
-            _ => parse_op_token(op, &mut op_codes, &mut op_hints, i)?,
+            _ => {
+                let next = parse_op_token(op, &mut op_codes, &mut op_hints, i)?;
+                span_length += 1;
+                if span_length > ctx.options.max_span_length() {
+                    return Err(AssemblyError::span_too_long(
+                        tokens[i],
+                        i,
+                        ctx.options.max_span_length(),
+                    ));
+                }
+                next
+            }
         };
     }
 
@@ -228,50 +277,72 @@ fn parse_op_token(
     step: usize,
 ) -> Result<usize, AssemblyError> {
     // based on the instruction, invoke the correct parser for the operation
-    match op[0] {
-        "noop" => parse_noop(op_codes, &op, step),
-        "assert" => parse_assert(op_codes, &op, step),
-
-        "push" => parse_push(op_codes, op_hints, &op, step),
-        "read" => parse_read(op_codes, &op, step),
-
-        "dup" => parse_dup(op_codes, &op, step),
-        "pad" => parse_pad(op_codes, &op, step),
-        "pick" => parse_pick(op_codes, &op, step),
-        "drop" => parse_drop(op_codes, &op, step),
-        "swap" => parse_swap(op_codes, &op, step),
-        "roll" => parse_roll(op_codes, &op, step),
-
-        "add" => parse_add(op_codes, &op, step),
-        "sub" => parse_sub(op_codes, &op, step),
-        "mul" => parse_mul(op_codes, &op, step),
-        "div" => parse_div(op_codes, &op, step),
-        "neg" => parse_neg(op_codes, &op, step),
-        "inv" => parse_inv(op_codes, &op, step),
-        "not" => parse_not(op_codes, &op, step),
-        "and" => parse_and(op_codes, &op, step),
-        "or" => parse_or(op_codes, &op, step),
-
-        "eq" => parse_eq(op_codes, op_hints, &op, step),
-        "ne" => parse_ne(op_codes, op_hints, &op, step),
-        "gt" => parse_gt(op_codes, op_hints, &op, step),
-        "lt" => parse_lt(op_codes, op_hints, &op, step),
-        "rc" => parse_rc(op_codes, op_hints, &op, step),
-        "isodd" => parse_isodd(op_codes, op_hints, &op, step),
-
-        "choose" => parse_choose(op_codes, &op, step),
-
-        "hash" => parse_hash(op_codes, &op, step),
-        "smpath" => parse_smpath(op_codes, &op, step),
-        "pmpath" => parse_pmpath(op_codes, op_hints, &op, step),
-
-        _ => return Err(AssemblyError::invalid_op(&op, step)),
-    }?;
+    match get_instruction(op[0]) {
+        Some(instruction) => instruction.parse(op_codes, op_hints, &op, step)?,
+        None => return Err(AssemblyError::invalid_op(&op, step)),
+    }
 
     // advance instruction pointer to the next step
     Ok(step + 1)
 }
 
+// PARSER CONTEXT
+// ================================================================================================
+
+/// Tracks nesting of blocks being parsed to enforce limits specified by [AssemblyOptions].
+struct ParserContext<'a> {
+    options: &'a AssemblyOptions,
+    block_depth: usize,
+    loop_depth: usize,
+}
+
+impl<'a> ParserContext<'a> {
+    fn new(options: &'a AssemblyOptions) -> Self {
+        ParserContext {
+            options,
+            block_depth: 0,
+            loop_depth: 0,
+        }
+    }
+
+    /// Increments nesting depth and returns an error if it exceeds the specified limits.
+    fn enter_block(
+        &mut self,
+        head: &[&str],
+        step: usize,
+        is_loop: bool,
+    ) -> Result<(), AssemblyError> {
+        self.block_depth += 1;
+        if self.block_depth > self.options.max_block_depth() {
+            return Err(AssemblyError::block_depth_exceeded(
+                head,
+                step,
+                self.options.max_block_depth(),
+            ));
+        }
+
+        if is_loop {
+            self.loop_depth += 1;
+            if self.loop_depth > self.options.max_loop_depth() {
+                return Err(AssemblyError::loop_depth_exceeded(
+                    head,
+                    step,
+                    self.options.max_loop_depth(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn exit_block(&mut self, is_loop: bool) {
+        self.block_depth -= 1;
+        if is_loop {
+            self.loop_depth -= 1;
+        }
+    }
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
@@ -0,0 +1,88 @@
+use vm_core::{MAX_CONTEXT_DEPTH, MAX_LOOP_DEPTH};
+
+// ASSEMBLY OPTIONS
+// ================================================================================================
+
+/// Limits which a program must satisfy to be compiled.
+///
+/// By default, nesting limits are set to the maximum nesting supported by the VM, and thus, a
+/// program which compiles with default options does not overflow context or loop stacks during
+/// execution. Span length is not limited by default.
+#[derive(Clone, Debug)]
+pub struct AssemblyOptions {
+    max_block_depth: usize,
+    max_loop_depth: usize,
+    max_span_length: usize,
+}
+
+impl AssemblyOptions {
+    /// Returns options with limits set to the maximums supported by the VM.
+    pub fn new() -> Self {
+        AssemblyOptions {
+            max_block_depth: MAX_CONTEXT_DEPTH - 1,
+            max_loop_depth: MAX_LOOP_DEPTH,
+            max_span_length: usize::MAX,
+        }
+    }
+
+    /// Returns these options modified with the maximum nesting depth of program blocks (of any
+    /// type) within the program.
+    ///
+    /// # Panics
+    /// Panics if `depth` is greater than the nesting depth supported by the VM.
+    pub fn with_max_block_depth(mut self, depth: usize) -> Self {
+        assert!(
+            depth < MAX_CONTEXT_DEPTH,
+            "max block depth cannot be greater than {}, but was {}",
+            MAX_CONTEXT_DEPTH - 1,
+            depth
+        );
+        self.max_block_depth = depth;
+        self
+    }
+
+    /// Returns these options modified with the maximum nesting depth of loops within the program.
+    ///
+    /// # Panics
+    /// Panics if `depth` is greater than the loop nesting depth supported by the VM.
+    pub fn with_max_loop_depth(mut self, depth: usize) -> Self {
+        assert!(
+            depth <= MAX_LOOP_DEPTH,
+            "max loop depth cannot be greater than {}, but was {}",
+            MAX_LOOP_DEPTH,
+            depth
+        );
+        self.max_loop_depth = depth;
+        self
+    }
+
+    /// Returns these options modified with the maximum number of instructions in a sequence of
+    /// instructions between block boundaries. Only instructions present in the source code are
+    /// counted: operations which the assembler adds at the start of a block or for alignment are
+    /// not.
+    pub fn with_max_span_length(mut self, length: usize) -> Self {
+        self.max_span_length = length;
+        self
+    }
+
+    /// Returns the maximum nesting depth of program blocks.
+    pub fn max_block_depth(&self) -> usize {
+        self.max_block_depth
+    }
+
+    /// Returns the maximum nesting depth of loops.
+    pub fn max_loop_depth(&self) -> usize {
+        self.max_loop_depth
+    }
+
+    /// Returns the maximum number of instructions between block boundaries.
+    pub fn max_span_length(&self) -> usize {
+        self.max_span_length
+    }
+}
+
+impl Default for AssemblyOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,9 +1,42 @@
-use super::{AssemblyError, BaseElement, FieldElement, HintMap, OpCode, OpHint, StarkField};
+use super::{
+    AssemblyError, BaseElement, FieldElement, HintMap, OpCode, OpHint, ProgramSignature,
+    StarkField, MAX_OUTPUTS, MAX_PUBLIC_INPUTS,
+};
 
 // CONSTANTS
 // ================================================================================================
 const PUSH_OP_ALIGNMENT: usize = 8;
 const HASH_OP_ALIGNMENT: usize = 16;
+const MAX_PUSH_VALUES: usize = 4;
+const HEX_CHARS_PER_ELEMENT: usize = 32;
+
+// PROGRAM SIGNATURE
+// ================================================================================================
+
+/// Parses inputs and outputs declared in the program head (e.g. `begin.inputs.4.outputs.2`);
+/// both declarations are optional, but if both are present, inputs must come first.
+pub fn parse_signature(head: &[&str]) -> Result<ProgramSignature, AssemblyError> {
+    let mut signature = ProgramSignature::new();
+    let mut params = &head[1..];
+
+    if params.first() == Some(&"inputs") {
+        let num_inputs = read_signature_param(head, params.get(1), MAX_PUBLIC_INPUTS)?;
+        signature = signature.with_inputs(num_inputs);
+        params = &params[2..];
+    }
+
+    if params.first() == Some(&"outputs") {
+        let num_outputs = read_signature_param(head, params.get(1), MAX_OUTPUTS)?;
+        signature = signature.with_outputs(num_outputs);
+        params = &params[2..];
+    }
+
+    if !params.is_empty() {
+        return Err(AssemblyError::invalid_block_head(head, 0));
+    }
+
+    Ok(signature)
+}
 
 // CONTROL FLOW OPERATIONS
 // ================================================================================================
@@ -47,15 +80,17 @@ pub fn parse_assert(
 // INPUT OPERATIONS
 // ================================================================================================
 
-/// Appends a PUSH operation to the program.
+/// Appends one or more PUSH operations to the program; values are pushed in the order in which
+/// they are specified.
 pub fn parse_push(
     program: &mut Vec<OpCode>,
     hints: &mut HintMap,
     op: &[&str],
     step: usize,
 ) -> Result<(), AssemblyError> {
-    let value = read_value(op, step)?;
-    append_push_op(program, hints, value);
+    for value in read_values(op, step)? {
+        append_push_op(program, hints, value);
+    }
     Ok(())
 }
 
@@ -99,6 +134,49 @@ pub fn parse_read(
     Ok(())
 }
 
+/// Appends a NOOP operation to the program with a hint instructing the processor to check that
+/// the top of the stack matches the specified values; the check is performed only when the
+/// processor is built with debug assertions enabled.
+pub fn parse_assert_stack_top(
+    program: &mut Vec<OpCode>,
+    hints: &mut HintMap,
+    op: &[&str],
+    step: usize,
+) -> Result<(), AssemblyError> {
+    let values = read_values(op, step)?;
+    let mut expected = [BaseElement::ZERO; MAX_PUSH_VALUES];
+    expected[..values.len()].copy_from_slice(&values);
+
+    hints.insert(
+        program.len(),
+        OpHint::AssertStackTop(values.len() as u32, expected),
+    );
+    program.push(OpCode::Noop);
+    Ok(())
+}
+
+/// Appends CLK operation to the program.
+pub fn parse_clk(program: &mut Vec<OpCode>, op: &[&str], step: usize) -> Result<(), AssemblyError> {
+    if op.len() > 1 {
+        return Err(AssemblyError::extra_param(op, step));
+    }
+    program.push(OpCode::Clk);
+    Ok(())
+}
+
+/// Appends SDEPTH operation to the program.
+pub fn parse_sdepth(
+    program: &mut Vec<OpCode>,
+    op: &[&str],
+    step: usize,
+) -> Result<(), AssemblyError> {
+    if op.len() > 1 {
+        return Err(AssemblyError::extra_param(op, step));
+    }
+    program.push(OpCode::SDepth);
+    Ok(())
+}
+
 // STACK MANIPULATION OPERATIONS
 // ================================================================================================
 
@@ -258,6 +336,73 @@ pub fn parse_roll(
     Ok(())
 }
 
+/// Appends a sequence of operations to the program to move n-th word (a pair of stack items) to
+/// the top of the stack.
+pub fn parse_movupw(
+    program: &mut Vec<OpCode>,
+    op: &[&str],
+    step: usize,
+) -> Result<(), AssemblyError> {
+    let n = read_param(op, step)?;
+    match n {
+        2 => program.extend_from_slice(&[
+            OpCode::Swap4,
+            OpCode::Swap2,
+            OpCode::Swap4,
+            OpCode::Roll8,
+            OpCode::Roll8,
+        ]),
+        3 => program.extend_from_slice(&[OpCode::Roll8, OpCode::Roll8]),
+        _ => {
+            return Err(AssemblyError::invalid_param_reason(
+                op,
+                step,
+                format!("parameter {} is invalid; allowed values are: [2, 3]", n),
+            ))
+        }
+    }
+
+    Ok(())
+}
+
+/// Appends a sequence of operations to the program to move the top word (a pair of stack items)
+/// to the n-th word position in the stack.
+pub fn parse_movdnw(
+    program: &mut Vec<OpCode>,
+    op: &[&str],
+    step: usize,
+) -> Result<(), AssemblyError> {
+    let n = read_param(op, step)?;
+    match n {
+        2 => {
+            program.extend_from_slice(&[OpCode::Roll8, OpCode::Roll8, OpCode::Swap2, OpCode::Swap4])
+        }
+        3 => program.extend_from_slice(&[OpCode::Swap4, OpCode::Roll8, OpCode::Roll8]),
+        _ => {
+            return Err(AssemblyError::invalid_param_reason(
+                op,
+                step,
+                format!("parameter {} is invalid; allowed values are: [2, 3]", n),
+            ))
+        }
+    }
+
+    Ok(())
+}
+
+/// Appends SWAP4 operation to the program to swap top two double-words of the stack.
+pub fn parse_swapdw(
+    program: &mut Vec<OpCode>,
+    op: &[&str],
+    step: usize,
+) -> Result<(), AssemblyError> {
+    if op.len() > 1 {
+        return Err(AssemblyError::extra_param(op, step));
+    }
+    program.push(OpCode::Swap4);
+    Ok(())
+}
+
 // ARITHMETIC AND BOOLEAN OPERATIONS
 // ================================================================================================
 
@@ -587,6 +732,58 @@ pub fn parse_choose(
     Ok(())
 }
 
+/// Appends CSWAP operation to the program.
+pub fn parse_cswap(
+    program: &mut Vec<OpCode>,
+    op: &[&str],
+    step: usize,
+) -> Result<(), AssemblyError> {
+    if op.len() > 1 {
+        return Err(AssemblyError::extra_param(op, step));
+    }
+    program.push(OpCode::CSwap);
+    Ok(())
+}
+
+/// Appends CSWAPW operation to the program.
+pub fn parse_cswapw(
+    program: &mut Vec<OpCode>,
+    op: &[&str],
+    step: usize,
+) -> Result<(), AssemblyError> {
+    if op.len() > 1 {
+        return Err(AssemblyError::extra_param(op, step));
+    }
+    program.push(OpCode::CSwapW);
+    Ok(())
+}
+
+/// Appends CSWAP DROP operations to the program.
+pub fn parse_cdrop(
+    program: &mut Vec<OpCode>,
+    op: &[&str],
+    step: usize,
+) -> Result<(), AssemblyError> {
+    if op.len() > 1 {
+        return Err(AssemblyError::extra_param(op, step));
+    }
+    program.extend_from_slice(&[OpCode::CSwap, OpCode::Drop]);
+    Ok(())
+}
+
+/// Appends CSWAPW DROP DROP operations to the program.
+pub fn parse_cdropw(
+    program: &mut Vec<OpCode>,
+    op: &[&str],
+    step: usize,
+) -> Result<(), AssemblyError> {
+    if op.len() > 1 {
+        return Err(AssemblyError::extra_param(op, step));
+    }
+    program.extend_from_slice(&[OpCode::CSwapW, OpCode::Drop, OpCode::Drop]);
+    Ok(())
+}
+
 // CRYPTO OPERATIONS
 // ================================================================================================
 
@@ -614,6 +811,66 @@ pub fn parse_hash(
         }
     }
 
+    // apply Rescue permutation to the state and truncate the state
+    append_rescue_permutation(program);
+    program.push(OpCode::Drop4);
+
+    Ok(())
+}
+
+/// Appends a sequence of operations to the program to merge two hash digests into a single
+/// digest.
+///
+/// The stack is expected to be arranged as follows (from the top):
+///
+/// [a1, a0, b1, b0, ...]
+///
+/// where a and b are the digests to be merged. After the operation is executed, the stack will
+/// be arranged as [c1, c0, ...] where c = hash(b, a). This is equivalent to `hash.4`.
+pub fn parse_hmerge(
+    program: &mut Vec<OpCode>,
+    op: &[&str],
+    step: usize,
+) -> Result<(), AssemblyError> {
+    if op.len() > 1 {
+        return Err(AssemblyError::extra_param(op, step));
+    }
+
+    // pad the stack with capacity elements, apply Rescue permutation, and truncate the state
+    program.push(OpCode::Pad2);
+    append_rescue_permutation(program);
+    program.push(OpCode::Drop4);
+
+    Ok(())
+}
+
+/// Appends a sequence of operations to the program to apply Rescue permutation to the top 6
+/// items of the stack.
+///
+/// The stack is expected to be arranged as follows (from the top):
+///
+/// [c1, c0, r3, r2, r1, r0, ...]
+///
+/// where c0 and c1 are the capacity elements of the hasher state, and r0 - r3 are the rate
+/// elements of the hasher state. After the operation is executed, the top 6 items of the stack
+/// will contain the permuted state in the same arrangement; the rest of the stack is not
+/// affected. The digest of the state is located in the 5th and 6th stack items.
+pub fn parse_hperm(
+    program: &mut Vec<OpCode>,
+    op: &[&str],
+    step: usize,
+) -> Result<(), AssemblyError> {
+    if op.len() > 1 {
+        return Err(AssemblyError::extra_param(op, step));
+    }
+
+    append_rescue_permutation(program);
+    Ok(())
+}
+
+/// Makes sure Rescue permutation is properly aligned, and appends operations to execute 10 rounds
+/// of Rescue to the program.
+fn append_rescue_permutation(program: &mut Vec<OpCode>) {
     // pad with NOOPs to make sure hashing starts on a step which is a multiple of 16
     let alignment = program.len() % HASH_OP_ALIGNMENT;
     let pad_length = (HASH_OP_ALIGNMENT - alignment) % HASH_OP_ALIGNMENT;
@@ -632,11 +889,6 @@ pub fn parse_hash(
         OpCode::RescR,
         OpCode::RescR,
     ]);
-
-    // truncate the state
-    program.push(OpCode::Drop4);
-
-    Ok(())
 }
 
 /// Appends a sequence of operations to the program to compute the root of Merkle authentication
@@ -832,26 +1084,82 @@ fn read_param(op: &[&str], step: usize) -> Result<u32, AssemblyError> {
     Ok(result)
 }
 
-fn read_value(op: &[&str], step: usize) -> Result<BaseElement, AssemblyError> {
-    // make sure exactly 1 parameter was supplied
+fn read_values(op: &[&str], step: usize) -> Result<Vec<BaseElement>, AssemblyError> {
+    // make sure at least 1 parameter was supplied
     if op.len() == 1 {
         return Err(AssemblyError::missing_param(op, step));
-    } else if op.len() > 2 {
-        return Err(AssemblyError::extra_param(op, step));
     }
 
-    let result = if op[1].starts_with("0x") {
-        // parse hexadecimal number
-        match u128::from_str_radix(&op[1][2..], 16) {
-            Ok(i) => i,
-            Err(_) => return Err(AssemblyError::invalid_param(op, step)),
-        }
-    } else {
-        // parse decimal number
-        match op[1].parse::<u128>() {
-            Ok(i) => i,
-            Err(_) => return Err(AssemblyError::invalid_param(op, step)),
+    let mut result = Vec::with_capacity(MAX_PUSH_VALUES);
+    for &param in op[1..].iter() {
+        if param.starts_with("0x") && param.len() > 2 + HEX_CHARS_PER_ELEMENT {
+            // a long hexadecimal number is decoded into several elements, one element per
+            // 32 hex characters, with the first element encoded by the first 32 characters
+            let digits = &param[2..];
+            if digits.len() % HEX_CHARS_PER_ELEMENT != 0 || !digits.is_ascii() {
+                return Err(AssemblyError::invalid_param_reason(
+                    op,
+                    step,
+                    format!(
+                        "parameter {} is invalid; hex values spanning multiple elements must consist of {}-character chunks",
+                        param, HEX_CHARS_PER_ELEMENT
+                    ),
+                ));
+            }
+            for i in (0..digits.len()).step_by(HEX_CHARS_PER_ELEMENT) {
+                let chunk = &digits[i..(i + HEX_CHARS_PER_ELEMENT)];
+                result.push(parse_element(op, chunk, 16, step)?);
+            }
+        } else if let Some(digits) = param.strip_prefix("0x") {
+            result.push(parse_element(op, digits, 16, step)?);
+        } else {
+            result.push(parse_element(op, param, 10, step)?);
         }
+    }
+
+    if result.len() > MAX_PUSH_VALUES {
+        return Err(AssemblyError::invalid_param_reason(
+            op,
+            step,
+            format!(
+                "at most {} values can be pushed by a single instruction, but {} were provided",
+                MAX_PUSH_VALUES,
+                result.len()
+            ),
+        ));
+    }
+
+    Ok(result)
+}
+
+fn read_signature_param(
+    head: &[&str],
+    param: Option<&&str>,
+    max_value: usize,
+) -> Result<usize, AssemblyError> {
+    let param = param.ok_or_else(|| AssemblyError::missing_param(head, 0))?;
+    match param.parse::<usize>() {
+        Ok(value) if value <= max_value => Ok(value),
+        _ => Err(AssemblyError::invalid_param_reason(
+            head,
+            0,
+            format!(
+                "parameter {} is invalid; value must be an integer between 0 and {}",
+                param, max_value
+            ),
+        )),
+    }
+}
+
+fn parse_element(
+    op: &[&str],
+    digits: &str,
+    radix: u32,
+    step: usize,
+) -> Result<BaseElement, AssemblyError> {
+    let result = match u128::from_str_radix(digits, radix) {
+        Ok(i) => i,
+        Err(_) => return Err(AssemblyError::invalid_param(op, step)),
     };
 
     // make sure the value is a valid field element
@@ -1,3 +1,5 @@
+use super::{AssemblyErrorKind, AssemblyOptions};
+
 // GROUP BLOCKS
 // ================================================================================================
 #[test]
@@ -402,3 +404,342 @@ fn repeat_2_blocks_with_suffix() {
 
     assert_eq!(expected, format!("{:?}", program));
 }
+
+// PROGRAM SIGNATURE
+// ================================================================================================
+
+#[test]
+fn program_signature() {
+    let program = super::compile("begin.inputs.2.outputs.1 add end").unwrap();
+    assert_eq!(Some(2), program.signature().num_inputs());
+    assert_eq!(Some(1), program.signature().num_outputs());
+
+    // the signature does not affect the program itself
+    let other = super::compile("begin add end").unwrap();
+    assert_eq!(None, other.signature().num_inputs());
+    assert_eq!(None, other.signature().num_outputs());
+    assert_eq!(program.hash(), other.hash());
+
+    let program = super::compile("begin.outputs.3 push.1 push.2 push.3 end").unwrap();
+    assert_eq!(None, program.signature().num_inputs());
+    assert_eq!(Some(3), program.signature().num_outputs());
+}
+
+// INPUT OPERATIONS
+// ================================================================================================
+
+#[test]
+fn push_multiple_values() {
+    let source = "begin \
+        push.1.2 \
+        push.0x0000000000000000000000000000000100000000000000000000000000000002 \
+        add end";
+    let program = super::compile(source).unwrap();
+
+    let expected = "\
+        begin noop noop noop noop noop noop noop \
+        push(1) noop noop noop noop noop noop noop \
+        push(2) noop noop noop noop noop noop noop \
+        push(1) noop noop noop noop noop noop noop \
+        push(2) add noop noop noop noop noop noop \
+        noop noop noop noop noop noop noop end";
+
+    assert_eq!(expected, format!("{:?}", program));
+
+    assert_assembly_error(
+        "begin push.1.2.3.4.5 end",
+        AssemblyErrorKind::InvalidParam,
+        1,
+    );
+    assert_assembly_error(
+        "begin push.0x000000000000000000000000000000010 end",
+        AssemblyErrorKind::InvalidParam,
+        1,
+    );
+}
+
+#[test]
+fn assert_stack_top() {
+    let program = super::compile("begin push.3 assert_stack_top.3.0 end").unwrap();
+    assert!(format!("{:?}", program).contains("push(3) noop.3.0 noop"));
+
+    assert_assembly_error(
+        "begin assert_stack_top end",
+        AssemblyErrorKind::MissingParam,
+        1,
+    );
+    assert_assembly_error(
+        "begin assert_stack_top.1.2.3.4.5 end",
+        AssemblyErrorKind::InvalidParam,
+        1,
+    );
+}
+
+// STACK MANIPULATION OPERATIONS
+// ================================================================================================
+
+#[test]
+fn word_moves() {
+    let source = "begin movupw.2 movupw.3 movdnw.2 movdnw.3 swapdw end";
+    let program = super::compile(source).unwrap();
+
+    let expected = "\
+        begin swap4 swap2 swap4 roll8 roll8 roll8 roll8 \
+        roll8 roll8 swap2 swap4 swap4 roll8 roll8 swap4 \
+        noop noop noop noop noop noop noop noop \
+        noop noop noop noop noop noop noop end";
+
+    assert_eq!(expected, format!("{:?}", program));
+}
+
+// CONDITIONAL OPERATIONS
+// ================================================================================================
+
+#[test]
+fn cswap_and_cdrop() {
+    let source = "begin cswap cswapw cdrop cdropw end";
+    let program = super::compile(source).unwrap();
+
+    let expected = "\
+        begin cswap cswapw cswap drop cswapw drop drop \
+        noop noop noop noop noop noop noop end";
+
+    assert_eq!(expected, format!("{:?}", program));
+}
+
+// CRYPTO OPERATIONS
+// ================================================================================================
+
+#[test]
+fn hmerge() {
+    let source = "begin hmerge end";
+    let program = super::compile(source).unwrap();
+
+    let expected = "\
+    begin \
+        pad2 noop noop noop noop noop noop \
+        noop noop noop noop noop noop noop noop \
+        rescr rescr rescr rescr rescr rescr rescr rescr \
+        rescr rescr drop4 noop noop noop noop \
+    end";
+
+    assert_eq!(expected, format!("{:?}", program));
+}
+
+#[test]
+fn hperm() {
+    let source = "begin hperm end";
+    let program = super::compile(source).unwrap();
+
+    let expected = "\
+    begin \
+        noop noop noop noop noop noop noop noop \
+        noop noop noop noop noop noop noop \
+        rescr rescr rescr rescr rescr rescr rescr rescr \
+        rescr rescr noop noop noop noop noop \
+    end";
+
+    assert_eq!(expected, format!("{:?}", program));
+}
+
+// INSTRUCTION COSTS
+// ================================================================================================
+
+#[test]
+fn instruction_costs() {
+    use super::instructions::Params;
+    let compile_op = |op: &str| super::compile(&format!("begin {} end", op));
+
+    let costs = super::instruction_costs();
+    let get_cost = |instruction: &str| {
+        let cost = costs.iter().find(|c| c.instruction == instruction).unwrap();
+        (cost.min_cycles, cost.max_cycles)
+    };
+
+    assert_eq!((1, 1), get_cost("add"));
+    assert_eq!((3, 3), get_cost("ne"));
+    assert_eq!((1, 8), get_cost("push.0"));
+    assert_eq!((12, 27), get_cost("hash.4"));
+
+    // every registered instruction must be covered by the table
+    for instruction in super::instructions::INSTRUCTION_SET.iter() {
+        let name = instruction.name();
+        assert!(
+            costs
+                .iter()
+                .any(|c| c.instruction.split('.').next() == Some(name)),
+            "no cost for instruction {}",
+            name
+        );
+
+        // parameter ranges must match the ranges accepted by the parser
+        if let Params::Range(min, max) = instruction.params() {
+            assert!(compile_op(&format!("{}.{}", name, max + 1)).is_err());
+            if *min > 1 {
+                assert!(compile_op(&format!("{}.{}", name, min - 1)).is_err());
+            }
+        }
+    }
+
+    let json = super::instruction_costs_json();
+    assert!(json.contains("{\"instruction\": \"add\", \"min_cycles\": 1, \"max_cycles\": 1}"));
+}
+
+// ERRORS
+// ================================================================================================
+
+#[test]
+fn instruction_set() {
+    use super::instructions::{BLOCK_KEYWORDS, INSTRUCTION_SET};
+
+    // every instruction is registered exactly once and does not clash with block keywords
+    let mut names = INSTRUCTION_SET.iter().map(|i| i.name()).collect::<Vec<_>>();
+    names.sort_unstable();
+    names.dedup();
+    assert_eq!(INSTRUCTION_SET.len(), names.len());
+    assert!(names.iter().all(|name| !BLOCK_KEYWORDS.contains(name)));
+
+    // suggestions are drawn from both instructions and block keywords
+    let error = super::compile("begin push.1 whilee.true end end").unwrap_err();
+    assert_eq!(Some("while"), error.suggestion());
+}
+
+#[test]
+fn invalid_op_suggestion() {
+    let error = super::compile("begin push.1 dupp end").unwrap_err();
+    assert_eq!(Some("dup"), error.suggestion());
+    assert_eq!(
+        "instruction dupp is invalid; did you mean `dup`?",
+        error.message()
+    );
+
+    let error = super::compile("begin push.1 foo end").unwrap_err();
+    assert_eq!(None, error.suggestion());
+}
+
+#[test]
+fn error_codes() {
+    let error = super::compile("begin push.1 foo end").unwrap_err();
+    assert_eq!("E0006", error.code());
+    assert_eq!(
+        "assembly error E0006 at 2: instruction foo is invalid",
+        error.to_string()
+    );
+
+    assert_eq!("E0001", AssemblyErrorKind::EmptyProgram.code());
+    assert_eq!("E0017", AssemblyErrorKind::UnmatchedElse.code());
+}
+
+#[test]
+fn program_structure_errors() {
+    assert_assembly_error("", AssemblyErrorKind::EmptyProgram, 0);
+    assert_assembly_error("push.1 end", AssemblyErrorKind::InvalidProgramStart, 0);
+    assert_assembly_error("begin push.1", AssemblyErrorKind::InvalidProgramEnd, 0);
+    assert_assembly_error("begin end", AssemblyErrorKind::EmptyBlock, 0);
+    assert_assembly_error(
+        "begin push.1 end push.2 end",
+        AssemblyErrorKind::DanglingInstructions,
+        2,
+    );
+}
+
+#[test]
+fn block_errors() {
+    assert_assembly_error(
+        "begin push.1 while.false add end end",
+        AssemblyErrorKind::InvalidBlockHead,
+        2,
+    );
+    assert_assembly_error(
+        "begin push.1 repeat.1 add end end",
+        AssemblyErrorKind::InvalidNumIterations,
+        2,
+    );
+    assert_assembly_error(
+        "begin push.1 else add end",
+        AssemblyErrorKind::DanglingElse,
+        2,
+    );
+    assert_assembly_error(
+        "begin push.1 block add end",
+        AssemblyErrorKind::UnmatchedBlock,
+        0,
+    );
+}
+
+#[test]
+fn instruction_errors() {
+    assert_assembly_error("begin push.1 foo end", AssemblyErrorKind::InvalidOp, 2);
+    assert_assembly_error("begin push end", AssemblyErrorKind::MissingParam, 1);
+    assert_assembly_error("begin push.1 clk.2 end", AssemblyErrorKind::ExtraParam, 2);
+    assert_assembly_error(
+        "begin push.1 read.b end",
+        AssemblyErrorKind::InvalidParam,
+        2,
+    );
+}
+
+#[test]
+fn signature_errors() {
+    assert_assembly_error(
+        "begin.outputs.1.inputs.2 add end",
+        AssemblyErrorKind::InvalidBlockHead,
+        0,
+    );
+    assert_assembly_error("begin.inputs add end", AssemblyErrorKind::MissingParam, 0);
+    assert_assembly_error("begin.inputs.9 add end", AssemblyErrorKind::InvalidParam, 0);
+    assert_assembly_error(
+        "begin.outputs.x add end",
+        AssemblyErrorKind::InvalidParam,
+        0,
+    );
+}
+
+#[test]
+fn limit_errors() {
+    // default limits match nesting supported by the VM
+    let source = format!(
+        "begin {} push.1 {} end",
+        "while.true ".repeat(9),
+        "end ".repeat(9)
+    );
+    assert_assembly_error(&source, AssemblyErrorKind::LoopDepthExceeded, 9);
+
+    let source = format!(
+        "begin {} push.1 {} end",
+        "block ".repeat(16),
+        "end ".repeat(16)
+    );
+    assert_assembly_error(&source, AssemblyErrorKind::BlockDepthExceeded, 16);
+
+    // custom limits
+    let options = AssemblyOptions::new().with_max_block_depth(1);
+    let source = "begin push.1 block block add end end end";
+    let error = super::compile_with_options(source, &options).unwrap_err();
+    assert_eq!(AssemblyErrorKind::BlockDepthExceeded, error.kind());
+    assert_eq!(3, error.step());
+
+    // only source instructions count towards span length: block heads and padding do not
+    let options = AssemblyOptions::new().with_max_span_length(3);
+    assert!(super::compile_with_options("begin add add add end", &options).is_ok());
+    let source = "begin push.1 push.2 push.3 if.true add add add end end";
+    assert!(super::compile_with_options(source, &options).is_ok());
+    let error = super::compile_with_options("begin add add add add end", &options).unwrap_err();
+    assert_eq!(AssemblyErrorKind::SpanTooLong, error.kind());
+    assert_eq!(4, error.step());
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Asserts that compiling the `source` fails with an error of the specified kind at the
+/// specified step.
+fn assert_assembly_error(source: &str, kind: AssemblyErrorKind, step: usize) {
+    match super::compile(source) {
+        Ok(_) => panic!("compilation of '{}' was expected to fail", source),
+        Err(error) => {
+            assert_eq!(kind, error.kind(), "unexpected error kind for '{}'", source);
+            assert_eq!(step, error.step(), "unexpected error step for '{}'", source);
+        }
+    }
+}
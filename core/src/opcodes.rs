@@ -57,29 +57,29 @@ pub enum UserOps {
     Choose2 = 0b0_11_00110,  // left shift: 4
     CSwap2 = 0b0_11_00111,   // left shift: 2
 
-    Add = 0b0_11_01000, // left shift: 1
-    Mul = 0b0_11_01001, // left shift: 1
-    And = 0b0_11_01010, // left shift: 1
-    Or = 0b0_11_01011,  // left shift: 1
-    Inv = 0b0_11_01100, // no shift
-    Neg = 0b0_11_01101, // no shift
-    Not = 0b0_11_01110, // no shift
-    //??? = 0b0_11_01111,
-    Read = 0b0_11_10000,  // right shift: 1
-    Read2 = 0b0_11_10001, // right shift: 2
-    Dup = 0b0_11_10010,   // right shift: 1
-    Dup2 = 0b0_11_10011,  // right shift: 2
-    Dup4 = 0b0_11_10100,  // right shift: 4
-    Pad2 = 0b0_11_10101,  // right shift: 2
-    //??? = 0b0_11_10110,
-    //??? = 0b0_11_10111,
+    Add = 0b0_11_01000,    // left shift: 1
+    Mul = 0b0_11_01001,    // left shift: 1
+    And = 0b0_11_01010,    // left shift: 1
+    Or = 0b0_11_01011,     // left shift: 1
+    Inv = 0b0_11_01100,    // no shift
+    Neg = 0b0_11_01101,    // no shift
+    Not = 0b0_11_01110,    // no shift
+    CSwap = 0b0_11_01111,  // left shift: 1
+    Read = 0b0_11_10000,   // right shift: 1
+    Read2 = 0b0_11_10001,  // right shift: 2
+    Dup = 0b0_11_10010,    // right shift: 1
+    Dup2 = 0b0_11_10011,   // right shift: 2
+    Dup4 = 0b0_11_10100,   // right shift: 4
+    Pad2 = 0b0_11_10101,   // right shift: 2
+    CSwapW = 0b0_11_10110, // left shift: 1
+    Clk = 0b0_11_10111,    // right shift: 1
     Swap = 0b0_11_11000,   // no shift
     Swap2 = 0b0_11_11001,  // no shift
     Swap4 = 0b0_11_11010,  // no shift
     Roll4 = 0b0_11_11011,  // no shift
     Roll8 = 0b0_11_11100,  // no shift
     BinAcc = 0b0_11_11101, // no shift
-    //??? = 0b0_11_11110,
+    SDepth = 0b0_11_11110, // right shift: 1
 
     // high-degree operations
     Push = 0b0_00_11111,  // right shift: 1
@@ -125,6 +125,8 @@ impl fmt::Display for UserOps {
             UserOps::Push => write!(f, "push"),
             UserOps::Read => write!(f, "read"),
             UserOps::Read2 => write!(f, "read2"),
+            UserOps::Clk => write!(f, "clk"),
+            UserOps::SDepth => write!(f, "sdepth"),
 
             UserOps::Dup => write!(f, "dup"),
             UserOps::Dup2 => write!(f, "dup2"),
@@ -144,6 +146,8 @@ impl fmt::Display for UserOps {
             UserOps::Choose => write!(f, "choose"),
             UserOps::Choose2 => write!(f, "choose2"),
             UserOps::CSwap2 => write!(f, "cswap2"),
+            UserOps::CSwap => write!(f, "cswap"),
+            UserOps::CSwapW => write!(f, "cswapw"),
 
             UserOps::Add => write!(f, "add"),
             UserOps::Mul => write!(f, "mul"),
@@ -164,13 +168,14 @@ impl fmt::Display for UserOps {
 
 // OPERATION HINTS
 // ================================================================================================
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub enum OpHint {
     EqStart,
     RcStart(u32),
     CmpStart(u32),
     PmpathStart(u32),
     PushValue(BaseElement),
+    AssertStackTop(u32, [BaseElement; 4]),
     None,
 }
 
@@ -191,6 +196,12 @@ impl fmt::Display for OpHint {
             OpHint::CmpStart(value) => write!(f, ".{}", value),
             OpHint::PmpathStart(value) => write!(f, ".{}", value),
             OpHint::PushValue(value) => write!(f, "({})", value),
+            OpHint::AssertStackTop(n, values) => {
+                for value in values.iter().take(*n as usize) {
+                    write!(f, ".{}", value)?;
+                }
+                Ok(())
+            }
             OpHint::None => Ok(()),
         }
     }
@@ -1,4 +1,6 @@
 use super::{BaseElement, MAX_PUBLIC_INPUTS};
+use crate::{utils::TapeRng, StarkField};
+use core::fmt;
 
 // PROGRAM INPUTS
 // ================================================================================================
@@ -7,19 +9,20 @@ use super::{BaseElement, MAX_PUBLIC_INPUTS};
 pub struct ProgramInputs {
     public: Vec<BaseElement>,
     secret: [Vec<BaseElement>; 2],
+    tape_seed: Option<u64>,
 }
 
 impl ProgramInputs {
-    /// Returns `ProgramInputs` initialized with the provided public and secret inputs.
+    /// Returns `ProgramInputs` initialized with the provided public and secret inputs; values
+    /// which are not valid field elements are reduced modulo the field modulus.
+    ///
+    /// # Panics
+    /// Panics if more than 8 public inputs were provided, or if secret tape A is shorter than
+    /// secret tape B.
     pub fn new(public: &[u128], secret_a: &[u128], secret_b: &[u128]) -> ProgramInputs {
-        assert!(
-            public.len() <= MAX_PUBLIC_INPUTS,
-            "expected no more than {} public inputs, but received {}",
-            MAX_PUBLIC_INPUTS,
-            public.len()
-        );
-        assert!(secret_a.len() >= secret_b.len(),
-            "number of primary secret inputs cannot be smaller than the number of secondary secret inputs");
+        if let Err(err) = check_lengths(public, secret_a, secret_b) {
+            panic!("{}", err);
+        }
 
         ProgramInputs {
             public: public.iter().map(|&v| BaseElement::new(v)).collect(),
@@ -27,14 +30,38 @@ impl ProgramInputs {
                 secret_a.iter().map(|&v| BaseElement::new(v)).collect(),
                 secret_b.iter().map(|&v| BaseElement::new(v)).collect(),
             ],
+            tape_seed: None,
         }
     }
 
+    /// Returns `ProgramInputs` initialized with the provided public and secret inputs, or an
+    /// error if:
+    /// * more than 8 public inputs were provided;
+    /// * secret tape A is shorter than secret tape B;
+    /// * any of the values is not a valid field element.
+    ///
+    /// Unlike [ProgramInputs::new()], this function does not reduce values which are not valid
+    /// field elements.
+    pub fn try_new(
+        public: &[u128],
+        secret_a: &[u128],
+        secret_b: &[u128],
+    ) -> Result<ProgramInputs, InputError> {
+        check_lengths(public, secret_a, secret_b)?;
+
+        Ok(ProgramInputs {
+            public: to_elements(public)?,
+            secret: [to_elements(secret_a)?, to_elements(secret_b)?],
+            tape_seed: None,
+        })
+    }
+
     /// Returns `ProgramInputs` with public and secret input tapes set to empty vectors.
     pub fn none() -> ProgramInputs {
         ProgramInputs {
             public: Vec::new(),
             secret: [Vec::new(), Vec::new()],
+            tape_seed: None,
         }
     }
 
@@ -44,9 +71,36 @@ impl ProgramInputs {
         ProgramInputs {
             public: public.iter().map(|&v| BaseElement::new(v)).collect(),
             secret: [vec![], vec![]],
+            tape_seed: None,
+        }
+    }
+
+    /// Returns `ProgramInputs` with no public inputs and both secret input tapes filled with
+    /// `tape_len` pseudo-random values generated from the specified `seed`.
+    ///
+    /// The same seed always produces the same tapes; this is intended for examples, tests, and
+    /// fuzzing where the actual values of secret inputs do not matter.
+    pub fn from_seed(seed: u64, tape_len: usize) -> ProgramInputs {
+        let mut rng = TapeRng::new(seed);
+        let tape_a = (0..tape_len).map(|_| rng.next_element()).collect();
+        let tape_b = (0..tape_len).map(|_| rng.next_element()).collect();
+        ProgramInputs {
+            public: Vec::new(),
+            secret: [tape_a, tape_b],
+            tape_seed: None,
         }
     }
 
+    /// Returns these inputs modified so that once a secret input tape runs out of values, reading
+    /// from it yields pseudo-random values generated from the specified `seed` instead of failing.
+    ///
+    /// This is intended for testing and fuzzing only: the same seed always produces the same
+    /// sequence of values, but these values are not related to any real inputs.
+    pub fn with_tape_seed(mut self, seed: u64) -> ProgramInputs {
+        self.tape_seed = Some(seed);
+        self
+    }
+
     pub fn public_inputs(&self) -> &[BaseElement] {
         &self.public
     }
@@ -54,4 +108,80 @@ impl ProgramInputs {
     pub fn secret_inputs(&self) -> &[Vec<BaseElement>; 2] {
         &self.secret
     }
+
+    pub fn tape_seed(&self) -> Option<u64> {
+        self.tape_seed
+    }
+}
+
+// INPUT ERROR
+// ================================================================================================
+
+/// Describes why a set of program inputs is invalid.
+#[derive(Clone, PartialEq)]
+pub enum InputError {
+    /// More than 8 public inputs were provided; contains the number of provided inputs.
+    TooManyPublicInputs(usize),
+    /// Secret tape A contains fewer values than secret tape B; contains lengths of both tapes.
+    SecretTapeTooShort(usize, usize),
+    /// The value is not a valid field element.
+    InvalidValue(u128),
+}
+
+impl fmt::Debug for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::TooManyPublicInputs(n) => write!(
+                f,
+                "expected no more than {} public inputs, but received {}",
+                MAX_PUBLIC_INPUTS, n
+            ),
+            InputError::SecretTapeTooShort(a, b) => write!(
+                f,
+                "number of primary secret inputs ({}) cannot be smaller than the number of secondary secret inputs ({})",
+                a, b
+            ),
+            InputError::InvalidValue(value) => write!(
+                f,
+                "input value {} is not a valid field element; values must be smaller than {}",
+                value,
+                BaseElement::MODULUS
+            ),
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn check_lengths(public: &[u128], secret_a: &[u128], secret_b: &[u128]) -> Result<(), InputError> {
+    if public.len() > MAX_PUBLIC_INPUTS {
+        return Err(InputError::TooManyPublicInputs(public.len()));
+    }
+    if secret_a.len() < secret_b.len() {
+        return Err(InputError::SecretTapeTooShort(
+            secret_a.len(),
+            secret_b.len(),
+        ));
+    }
+    Ok(())
+}
+
+fn to_elements(values: &[u128]) -> Result<Vec<BaseElement>, InputError> {
+    values
+        .iter()
+        .map(|&v| {
+            if v < BaseElement::MODULUS {
+                Ok(BaseElement::new(v))
+            } else {
+                Err(InputError::InvalidValue(v))
+            }
+        })
+        .collect()
 }
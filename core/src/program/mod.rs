@@ -10,11 +10,17 @@ pub mod blocks;
 use blocks::{Group, ProgramBlock};
 
 mod inputs;
-pub use inputs::ProgramInputs;
+pub use inputs::{InputError, ProgramInputs};
 
 mod hashing;
 use hashing::{hash_acc, hash_op, hash_seq};
 
+mod normalize;
+pub use normalize::{NormalBlock, NormalOp};
+
+mod signature;
+pub use signature::{ProgramSignature, SignatureError};
+
 #[cfg(test)]
 mod tests;
 
@@ -24,6 +30,7 @@ mod tests;
 pub struct Program {
     root: Group,
     hash: [u8; 32],
+    signature: ProgramSignature,
 }
 
 impl Program {
@@ -50,8 +57,17 @@ impl Program {
         Program {
             root,
             hash: hash_bytes,
+            signature: ProgramSignature::default(),
         }
     }
+
+    /// Returns this program with the specified signature; the signature does not affect the
+    /// program hash.
+    pub fn with_signature(mut self, signature: ProgramSignature) -> Program {
+        self.signature = signature;
+        self
+    }
+
     /// Returns the root block of the program.
     pub fn root(&self) -> &Group {
         &self.root
@@ -61,6 +77,41 @@ impl Program {
     pub fn hash(&self) -> &[u8; 32] {
         &self.hash
     }
+
+    /// Returns inputs and outputs declared by the program.
+    pub fn signature(&self) -> &ProgramSignature {
+        &self.signature
+    }
+
+    /// Returns a normalized representation of the program; see [NormalBlock] for details.
+    pub fn normalize(&self) -> Vec<NormalBlock> {
+        let mut result = Vec::new();
+        normalize::normalize_blocks(self.root.body(), &mut result);
+        result
+    }
+
+    /// Returns true if this program executes the same sequence of non-NOOP operations as the
+    /// `other` program for all inputs.
+    ///
+    /// Unlike comparing program hashes, this ignores superficial differences between programs
+    /// such as redundant nested blocks and NOOP padding. This has the following limitations:
+    /// * Such differences change the value of the op counter, which is observable via CLK
+    ///   operation; thus, if either of the programs contains CLK operations, the programs are
+    ///   considered equal only if their hashes are the same (i.e., the comparison is exact rather
+    ///   than structural).
+    /// * Results of RESCR operations depend on their alignment within the hash cycle; thus,
+    ///   RESCR operations are equal only if they are aligned in the same way, even if NOOP
+    ///   padding between them differs.
+    pub fn structurally_equal(&self, other: &Program) -> bool {
+        let root_blocks = [self.root.body(), other.root.body()];
+        if root_blocks
+            .iter()
+            .any(|blocks| normalize::contains_op(blocks, OpCode::Clk))
+        {
+            return self.hash == other.hash;
+        }
+        self.normalize() == other.normalize()
+    }
 }
 
 impl fmt::Debug for Program {
@@ -0,0 +1,92 @@
+use super::{
+    blocks::{ProgramBlock, Span},
+    OpCode, OpHint, BASE_CYCLE_LENGTH,
+};
+
+// NORMALIZED BLOCK
+// ================================================================================================
+
+/// A program block with superficial structure removed; used to compare programs structurally.
+///
+/// In a normalized program, bodies of Group blocks are inlined into the enclosing sequence,
+/// NOOPs (including the ones added for alignment and between blocks) are removed, and adjacent
+/// operation sequences are merged. Skip blocks of loops are omitted as they are fully determined
+/// by the loop itself.
+///
+/// Removing NOOPs changes the steps at which the remaining operations are executed. This does
+/// not affect results of most operations, but RESCR uses round constants selected by the step
+/// within the hash cycle; thus, every RESCR operation is recorded together with its position
+/// within the cycle (see [NormalOp]).
+#[derive(Clone, Debug, PartialEq)]
+pub enum NormalBlock {
+    Ops(Vec<NormalOp>),
+    Switch(Vec<NormalBlock>, Vec<NormalBlock>),
+    Loop(Vec<NormalBlock>),
+}
+
+/// An operation of a normalized program: the op code, the hint, and the position of the operation
+/// within the hash cycle for operations whose results depend on it (i.e., RESCR); the position is
+/// None for all other operations.
+pub type NormalOp = (OpCode, OpHint, Option<usize>);
+
+/// Appends normalized versions of the specified blocks to the `result`.
+pub fn normalize_blocks(blocks: &[ProgramBlock], result: &mut Vec<NormalBlock>) {
+    for block in blocks {
+        match block {
+            ProgramBlock::Span(block) => append_ops(block, result),
+            ProgramBlock::Group(block) => normalize_blocks(block.body(), result),
+            ProgramBlock::Switch(block) => {
+                let mut t_branch = Vec::new();
+                normalize_blocks(block.true_branch(), &mut t_branch);
+                let mut f_branch = Vec::new();
+                normalize_blocks(block.false_branch(), &mut f_branch);
+                result.push(NormalBlock::Switch(t_branch, f_branch));
+            }
+            ProgramBlock::Loop(block) => {
+                let mut body = Vec::new();
+                normalize_blocks(block.body(), &mut body);
+                result.push(NormalBlock::Loop(body));
+            }
+        }
+    }
+}
+
+/// Returns true if any of the specified blocks (or blocks nested in them) contains the specified
+/// operation.
+pub fn contains_op(blocks: &[ProgramBlock], op_code: OpCode) -> bool {
+    blocks.iter().any(|block| match block {
+        ProgramBlock::Span(block) => (0..block.length()).any(|i| block.get_op(i).0 == op_code),
+        ProgramBlock::Group(block) => contains_op(block.body(), op_code),
+        ProgramBlock::Switch(block) => {
+            contains_op(block.true_branch(), op_code) || contains_op(block.false_branch(), op_code)
+        }
+        ProgramBlock::Loop(block) => contains_op(block.body(), op_code),
+    })
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn append_ops(block: &Span, result: &mut Vec<NormalBlock>) {
+    // spans always start at the same position within the hash cycle, so the position of an
+    // operation within the cycle is determined by its index in the span
+    let ops = (0..block.length())
+        .map(|i| {
+            let (op_code, op_hint) = block.get_op(i);
+            match op_code {
+                OpCode::RescR => (op_code, op_hint, Some(i % BASE_CYCLE_LENGTH)),
+                _ => (op_code, op_hint, None),
+            }
+        })
+        .filter(|&(op_code, _, _)| op_code != OpCode::Noop);
+
+    match result.last_mut() {
+        Some(NormalBlock::Ops(prev_ops)) => prev_ops.extend(ops),
+        _ => {
+            let ops = ops.collect::<Vec<_>>();
+            if !ops.is_empty() {
+                result.push(NormalBlock::Ops(ops));
+            }
+        }
+    }
+}
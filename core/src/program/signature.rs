@@ -0,0 +1,104 @@
+use super::ProgramInputs;
+use core::fmt;
+
+// PROGRAM SIGNATURE
+// ================================================================================================
+
+/// Number of public inputs a program expects and number of outputs it leaves on the stack, as
+/// declared in the program source (e.g. `begin.inputs.4.outputs.2`).
+///
+/// Either of the values may be left undeclared, in which case it is not checked.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct ProgramSignature {
+    num_inputs: Option<usize>,
+    num_outputs: Option<usize>,
+}
+
+impl ProgramSignature {
+    /// Returns a signature which declares neither inputs nor outputs.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns this signature modified to declare the specified number of public inputs.
+    pub fn with_inputs(mut self, num_inputs: usize) -> Self {
+        self.num_inputs = Some(num_inputs);
+        self
+    }
+
+    /// Returns this signature modified to declare the specified number of outputs.
+    pub fn with_outputs(mut self, num_outputs: usize) -> Self {
+        self.num_outputs = Some(num_outputs);
+        self
+    }
+
+    /// Returns the declared number of public inputs, if any.
+    pub fn num_inputs(&self) -> Option<usize> {
+        self.num_inputs
+    }
+
+    /// Returns the declared number of outputs, if any.
+    pub fn num_outputs(&self) -> Option<usize> {
+        self.num_outputs
+    }
+
+    /// Returns an error if the number of public inputs in `inputs` differs from the declared
+    /// number of inputs.
+    pub fn check_inputs(&self, inputs: &ProgramInputs) -> Result<(), SignatureError> {
+        let actual = inputs.public_inputs().len();
+        match self.num_inputs {
+            Some(expected) if expected != actual => {
+                Err(SignatureError::InputCountMismatch(expected, actual))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns an error if the depth of the stack after program execution differs from the
+    /// declared number of outputs.
+    pub fn check_outputs(&self, stack_depth: usize) -> Result<(), SignatureError> {
+        match self.num_outputs {
+            Some(expected) if expected != stack_depth => {
+                Err(SignatureError::OutputCountMismatch(expected, stack_depth))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+// SIGNATURE ERROR
+// ================================================================================================
+
+/// Describes how program execution deviated from the program signature.
+#[derive(Clone, PartialEq)]
+pub enum SignatureError {
+    /// The number of public inputs differs from the declared one; contains the declared and the
+    /// actual number of inputs.
+    InputCountMismatch(usize, usize),
+    /// Stack depth after execution differs from the declared number of outputs; contains the
+    /// declared number of outputs and the actual stack depth.
+    OutputCountMismatch(usize, usize),
+}
+
+impl fmt::Debug for SignatureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl fmt::Display for SignatureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SignatureError::InputCountMismatch(expected, actual) => write!(
+                f,
+                "program declares {} public inputs, but received {}",
+                expected, actual
+            ),
+            SignatureError::OutputCountMismatch(expected, actual) => write!(
+                f,
+                "program declares {} outputs, but stack depth after execution is {}",
+                expected, actual
+            ),
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use super::{
     blocks::{Group, Loop, ProgramBlock, Span, Switch},
-    BaseElement, FieldElement, OpCode, Program,
+    BaseElement, FieldElement, InputError, OpCode, Program, ProgramInputs,
 };
 
 mod utils;
@@ -267,6 +267,102 @@ fn simple_loop() {
     assert_eq!(111, step);
 }
 
+#[test]
+fn structurally_equal_programs() {
+    let program = Program::new(Group::new(vec![build_span(&[
+        OpCode::Begin,
+        OpCode::Add,
+        OpCode::Mul,
+    ])]));
+
+    // redundant nested blocks and padding do not affect structural equality
+    let other = Program::new(Group::new(vec![
+        build_span(&[OpCode::Begin, OpCode::Add]),
+        Group::new_block(vec![build_span(&[OpCode::Mul])]),
+    ]));
+    assert_ne!(program.hash(), other.hash());
+    assert!(program.structurally_equal(&other));
+
+    // different control flow does
+    let other = Program::new(Group::new(vec![
+        build_span(&[OpCode::Begin, OpCode::Add]),
+        Switch::new_block(
+            vec![build_span(&[OpCode::Assert, OpCode::Mul])],
+            vec![build_span(&[OpCode::Not, OpCode::Assert, OpCode::Mul])],
+        ),
+    ]));
+    assert!(!program.structurally_equal(&other));
+
+    // padding changes the op counter, and thus, programs with CLK must be identical
+    let program = Program::new(Group::new(vec![build_span(&[OpCode::Begin, OpCode::Clk])]));
+    let other = Program::new(Group::new(vec![build_span(&[
+        OpCode::Begin,
+        OpCode::Noop,
+        OpCode::Clk,
+    ])]));
+    assert!(!program.structurally_equal(&other));
+    assert!(program.structurally_equal(&program.clone()));
+
+    // padding changes round constants used by RESCR, unless it preserves RESCR alignment
+    let program = Program::new(Group::new(vec![build_span(&[
+        OpCode::Begin,
+        OpCode::RescR,
+    ])]));
+    let other = Program::new(Group::new(vec![build_span(&[
+        OpCode::Begin,
+        OpCode::Noop,
+        OpCode::RescR,
+    ])]));
+    assert!(!program.structurally_equal(&other));
+
+    let other = Program::new(Group::new(vec![
+        build_span(&[OpCode::Begin, OpCode::RescR]),
+        Group::new_block(vec![build_span(&[])]),
+    ]));
+    assert_ne!(program.hash(), other.hash());
+    assert!(program.structurally_equal(&other));
+}
+
+#[test]
+fn inputs_from_seed() {
+    let inputs = ProgramInputs::from_seed(42, 5);
+    assert!(inputs.public_inputs().is_empty());
+    assert_eq!(5, inputs.secret_inputs()[0].len());
+    assert_eq!(5, inputs.secret_inputs()[1].len());
+    assert_ne!(inputs.secret_inputs()[0], inputs.secret_inputs()[1]);
+
+    // the same seed always produces the same tapes
+    let other = ProgramInputs::from_seed(42, 5);
+    assert_eq!(inputs.secret_inputs(), other.secret_inputs());
+
+    let other = ProgramInputs::from_seed(43, 5);
+    assert_ne!(inputs.secret_inputs(), other.secret_inputs());
+}
+
+#[test]
+fn invalid_inputs() {
+    assert!(ProgramInputs::try_new(&[1, 2, 3], &[4, 5], &[6]).is_ok());
+
+    let result = ProgramInputs::try_new(&[0; 9], &[], &[]);
+    assert_eq!(InputError::TooManyPublicInputs(9), result.unwrap_err());
+
+    let result = ProgramInputs::try_new(&[], &[1], &[2, 3]);
+    assert_eq!(InputError::SecretTapeTooShort(1, 2), result.unwrap_err());
+
+    let result = ProgramInputs::try_new(&[u128::MAX], &[], &[]);
+    assert_eq!(InputError::InvalidValue(u128::MAX), result.unwrap_err());
+
+    // new() reduces values which are not valid field elements instead of rejecting them
+    let inputs = ProgramInputs::new(&[u128::MAX], &[], &[]);
+    assert_eq!(&[BaseElement::new(u128::MAX)], inputs.public_inputs());
+}
+
+#[test]
+#[should_panic(expected = "expected no more than 8 public inputs, but received 9")]
+fn too_many_public_inputs() {
+    ProgramInputs::new(&[0; 9], &[], &[]);
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 fn build_first_block(op_code: OpCode, length: usize) -> ProgramBlock {
@@ -275,6 +371,12 @@ fn build_first_block(op_code: OpCode, length: usize) -> ProgramBlock {
     return Span::new_block(instructions);
 }
 
+fn build_span(op_codes: &[OpCode]) -> ProgramBlock {
+    let mut instructions = op_codes.to_vec();
+    instructions.resize(15, OpCode::Noop);
+    return Span::new_block(instructions);
+}
+
 fn hash_to_bytes(hash: &[BaseElement; 4]) -> [u8; 32] {
     let mut hash_bytes = [0u8; 32];
     hash_bytes.copy_from_slice(BaseElement::elements_as_bytes(&hash[..2]));
@@ -1,4 +1,4 @@
-use crate::BaseElement;
+use crate::{BaseElement, StarkField};
 use core::ops::Range;
 
 // RANGE
@@ -34,3 +34,31 @@ impl ToElements for Vec<u128> {
         self.iter().map(|&v| BaseElement::new(v)).collect()
     }
 }
+
+// TAPE RNG
+// ================================================================================================
+
+/// Deterministic generator of pseudo-random field elements for secret input tapes; this is based
+/// on SplitMix64 and is not suitable for anything other than testing.
+pub struct TapeRng {
+    state: u64,
+}
+
+impl TapeRng {
+    pub fn new(seed: u64) -> Self {
+        TapeRng { state: seed }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    pub fn next_element(&mut self) -> BaseElement {
+        let value = ((self.next_u64() as u128) << 64) | self.next_u64() as u128;
+        BaseElement::new(value % BaseElement::MODULUS)
+    }
+}
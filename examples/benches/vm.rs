@@ -0,0 +1,110 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use examples::{collatz, fibonacci, merkle, range, Example};
+use miden::{assembly, ProgramInputs, ProofOptions};
+use std::time::Duration;
+
+// PROGRAM CORPUS
+// ================================================================================================
+
+/// Sources of programs used to benchmark compilation; together these cover long straight-line
+/// blocks, nested control flow, and instructions which expand into many operations.
+const SOURCES: [(&str, &str); 3] = [
+    ("fibonacci", "begin repeat.1023 swap dup.2 drop add end end"),
+    (
+        "collatz",
+        "begin
+            pad read dup push.1 ne
+            while.true
+                swap push.1 add swap dup isodd.128
+                if.true
+                    push.3 mul push.1 add
+                else
+                    push.2 div
+                end
+                dup push.1 ne
+            end
+            swap
+        end",
+    ),
+    ("hashing", "begin repeat.128 hash.2 end end"),
+];
+
+/// Returns programs used to benchmark execution and proving, together with their inputs.
+fn get_examples() -> Vec<(&'static str, Example)> {
+    vec![
+        ("fibonacci", fibonacci::get_example(1024)),
+        ("collatz", collatz::get_example(511)),
+        ("hashing", get_hashing_example(128)),
+        ("merkle", merkle::get_example(20)),
+        ("range", range::get_example(100)),
+    ]
+}
+
+/// Returns a program which repeatedly hashes a 2-element value `n` times.
+fn get_hashing_example(n: usize) -> Example {
+    let source = format!("begin repeat.{} hash.2 end end", n);
+    Example {
+        program: assembly::compile(&source).unwrap(),
+        inputs: ProgramInputs::from_public(&[1, 2]),
+        pub_inputs: vec![1, 2],
+        num_outputs: 2,
+        expected_result: vec![],
+    }
+}
+
+// BENCHMARKS
+// ================================================================================================
+
+fn compile(c: &mut Criterion) {
+    let mut group = c.benchmark_group("compile");
+    for (name, source) in SOURCES.iter() {
+        group.bench_with_input(BenchmarkId::from_parameter(name), source, |b, source| {
+            b.iter(|| assembly::compile(source).unwrap());
+        });
+    }
+    group.finish();
+}
+
+fn execute(c: &mut Criterion) {
+    let mut group = c.benchmark_group("execute");
+    for (name, example) in get_examples().iter() {
+        group.bench_with_input(BenchmarkId::from_parameter(name), example, |b, example| {
+            b.iter(|| processor::execute_fast(&example.program, &example.inputs, 1).unwrap());
+        });
+    }
+    group.finish();
+}
+
+fn build_trace(c: &mut Criterion) {
+    let mut group = c.benchmark_group("build_trace");
+    for (name, example) in get_examples().iter() {
+        group.bench_with_input(BenchmarkId::from_parameter(name), example, |b, example| {
+            b.iter(|| processor::execute(&example.program, &example.inputs));
+        });
+    }
+    group.finish();
+}
+
+fn prove(c: &mut Criterion) {
+    let mut group = c.benchmark_group("prove");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(30));
+    let options = ProofOptions::with_96_bit_security();
+    for (name, example) in get_examples().iter() {
+        group.bench_with_input(BenchmarkId::from_parameter(name), example, |b, example| {
+            b.iter(|| {
+                miden::execute(
+                    &example.program,
+                    &example.inputs,
+                    example.num_outputs,
+                    &options,
+                )
+                .unwrap()
+            });
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(vm_group, compile, execute, build_trace, prove);
+criterion_main!(vm_group);
@@ -0,0 +1,179 @@
+use miden::{assembly, ConstraintFailure, ProgramInputs};
+use processor::ExecutionFailure;
+use std::{
+    fmt, fs, io,
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+};
+
+// CORPUS RUNNER
+// ================================================================================================
+
+/// Results of running all programs in a corpus directory.
+pub struct CorpusReport {
+    pub num_programs: usize,
+    pub failures: Vec<CorpusFailure>,
+}
+
+/// Describes a corpus program which did not produce the expected outputs.
+pub struct CorpusFailure {
+    pub name: String,
+    pub reason: FailureReason,
+}
+
+pub enum FailureReason {
+    InvalidInputs(String),
+    CompilationFailed(String),
+    ExecutionFailed(ExecutionFailure),
+    ConstraintFailed(ConstraintFailure),
+    OutputMismatch {
+        expected: Vec<u128>,
+        actual: Vec<u128>,
+    },
+}
+
+/// Executes every program in the specified directory and compares its outputs to the expected
+/// outputs.
+///
+/// Each program consists of the following files which share the same name:
+/// * `<name>.masm` - source code of the program;
+/// * `<name>.inputs` - optional program inputs; each line has the form `<tape>: <values>`, where
+///   tape is one of `public`, `secret_a`, `secret_b`, and values are separated by whitespace;
+/// * `<name>.outputs` - expected outputs (values at the top of the stack, starting with the
+///   topmost value) separated by whitespace.
+///
+/// Programs are executed via [miden::check_constraints()], and thus, every execution trace is
+/// also checked against AIR constraints.
+pub fn run_corpus(dir: &Path) -> io::Result<CorpusReport> {
+    let mut names = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().map_or(false, |ext| ext == "masm"))
+        .filter_map(|path| path.file_stem().map(|s| s.to_string_lossy().into_owned()))
+        .collect::<Vec<_>>();
+    names.sort();
+
+    let mut failures = Vec::new();
+    for name in names.iter() {
+        let source = fs::read_to_string(dir.join(format!("{}.masm", name)))?;
+        let inputs = read_optional(&dir.join(format!("{}.inputs", name)))?;
+        let outputs = fs::read_to_string(dir.join(format!("{}.outputs", name)))?;
+
+        if let Err(reason) = run_program(&source, &inputs, &outputs) {
+            failures.push(CorpusFailure {
+                name: name.clone(),
+                reason,
+            });
+        }
+    }
+
+    Ok(CorpusReport {
+        num_programs: names.len(),
+        failures,
+    })
+}
+
+fn run_program(source: &str, inputs: &str, outputs: &str) -> Result<(), FailureReason> {
+    let inputs = parse_inputs(inputs).map_err(FailureReason::InvalidInputs)?;
+    let expected = parse_values(outputs).map_err(FailureReason::InvalidInputs)?;
+    let program = assembly::compile(source)
+        .map_err(|err| FailureReason::CompilationFailed(err.to_string()))?;
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        miden::check_constraints(&program, &inputs, expected.len())
+    }))
+    .map_err(|err| FailureReason::ExecutionFailed(ExecutionFailure::from_panic(err)))?;
+
+    let actual = result.map_err(FailureReason::ConstraintFailed)?;
+    if actual != expected {
+        return Err(FailureReason::OutputMismatch { expected, actual });
+    }
+
+    Ok(())
+}
+
+// FORMATTING
+// ================================================================================================
+
+impl fmt::Display for CorpusFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.reason {
+            FailureReason::InvalidInputs(err) => {
+                write!(f, "{}: invalid input files: {}", self.name, err)
+            }
+            FailureReason::CompilationFailed(err) => write!(f, "{}: {}", self.name, err),
+            FailureReason::ExecutionFailed(err) => write!(f, "{}: {}", self.name, err),
+            FailureReason::ConstraintFailed(err) => write!(f, "{}: {}", self.name, err),
+            FailureReason::OutputMismatch { expected, actual } => {
+                write!(f, "{}: outputs do not match", self.name)?;
+                for (i, (e, a)) in expected.iter().zip(actual.iter()).enumerate() {
+                    if e != a {
+                        write!(f, "\n  [{}] expected {}, but was {}", i, e, a)?;
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn read_optional(path: &Path) -> io::Result<String> {
+    match fs::read_to_string(path) {
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(String::new()),
+        result => result,
+    }
+}
+
+fn parse_inputs(source: &str) -> Result<ProgramInputs, String> {
+    let mut tapes = [Vec::new(), Vec::new(), Vec::new()];
+    for line in source.lines().filter(|line| !line.trim().is_empty()) {
+        let (tape, values) = line
+            .split_once(':')
+            .ok_or_else(|| format!("line '{}' does not specify an input tape", line))?;
+        let idx = match tape.trim() {
+            "public" => 0,
+            "secret_a" => 1,
+            "secret_b" => 2,
+            other => return Err(format!("input tape '{}' is invalid", other)),
+        };
+        tapes[idx].extend(parse_values(values)?);
+    }
+
+    ProgramInputs::try_new(&tapes[0], &tapes[1], &tapes[2]).map_err(|err| err.to_string())
+}
+
+fn parse_values(source: &str) -> Result<Vec<u128>, String> {
+    source
+        .split_whitespace()
+        .map(|v| {
+            v.parse::<u128>()
+                .map_err(|_| format!("value '{}' is not a valid integer", v))
+        })
+        .collect()
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    #[test]
+    fn corpus() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("programs");
+        let report = super::run_corpus(&dir).unwrap();
+
+        assert!(
+            report.num_programs > 0,
+            "no programs found in {}",
+            dir.display()
+        );
+        for failure in report.failures.iter() {
+            eprintln!("{}", failure);
+        }
+        assert!(report.failures.is_empty());
+    }
+}
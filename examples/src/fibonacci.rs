@@ -72,3 +72,15 @@ fn test_fib_example_fail() {
     let example = get_example(16);
     super::test_example(example, true);
 }
+
+#[test]
+fn test_fib_example_batch() {
+    let examples = vec![get_example(16), get_example(32), get_example(16)];
+    super::test_example_batch(examples, None);
+}
+
+#[test]
+fn test_fib_example_batch_fail() {
+    let examples = vec![get_example(16), get_example(32), get_example(16)];
+    super::test_example_batch(examples, Some(1));
+}
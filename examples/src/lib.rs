@@ -4,6 +4,8 @@ use structopt::StructOpt;
 pub mod collatz;
 pub mod comparison;
 pub mod conditional;
+#[cfg(feature = "std")]
+pub mod corpus;
 pub mod fibonacci;
 #[cfg(feature = "std")]
 pub mod merkle;
@@ -33,6 +35,10 @@ pub struct ExampleOptions {
     /// Security level for execution proofs generated by the VM
     #[structopt(short = "s", long = "security", default_value = "96bits")]
     security: String,
+
+    /// Instead of proving, execute the program the specified number of times and report timings
+    #[structopt(short = "b", long = "bench")]
+    pub bench: Option<usize>,
 }
 
 impl ExampleOptions {
@@ -86,6 +92,20 @@ pub enum ExampleType {
         #[structopt(short = "n", default_value = "100")]
         num_values: usize,
     },
+    /// Compiles a program from the specified assembly file and prints its hash
+    #[cfg(feature = "std")]
+    Compile {
+        /// Path to the file with program source code
+        #[structopt(parse(from_os_str))]
+        file: std::path::PathBuf,
+    },
+    /// Runs all programs in the specified directory and compares their outputs to expected outputs
+    #[cfg(feature = "std")]
+    Corpus {
+        /// Path to the directory with programs, their inputs, and expected outputs
+        #[structopt(parse(from_os_str))]
+        dir: std::path::PathBuf,
+    },
 }
 
 // TESTS
@@ -101,16 +121,7 @@ pub fn test_example(example: Example, fail: bool) {
         expected_result,
     } = example;
 
-    let options = ProofOptions::new(
-        32,
-        8,
-        0,
-        miden::HashFunction::Blake3_256,
-        miden::FieldExtension::None,
-        8,
-        256,
-    );
-
+    let options = get_test_options();
     let (mut outputs, proof) = miden::execute(&program, &inputs, num_outputs, &options).unwrap();
 
     assert_eq!(
@@ -125,3 +136,73 @@ pub fn test_example(example: Example, fail: bool) {
         assert!(miden::verify(*program.hash(), &pub_inputs, &outputs, proof).is_ok());
     }
 }
+
+#[cfg(test)]
+pub fn test_example_batch(examples: Vec<Example>, fail_idx: Option<usize>) {
+    let options = get_test_options();
+
+    // bundles with invalid outputs are verified in addition to the bundles with incorrect outputs
+    // to make sure that invalid public inputs are reported as errors for the affected bundle
+    let mut bundles = Vec::with_capacity(examples.len());
+    let mut invalid_bundles = Vec::with_capacity(examples.len());
+    for (i, example) in examples.into_iter().enumerate() {
+        let Example {
+            program,
+            inputs,
+            pub_inputs,
+            num_outputs,
+            expected_result,
+        } = example;
+
+        let (mut outputs, proof) =
+            miden::execute(&program, &inputs, num_outputs, &options).unwrap();
+        assert_eq!(
+            expected_result, outputs,
+            "Program result was computed incorrectly"
+        );
+
+        let mut invalid_outputs = outputs.clone();
+        if fail_idx == Some(i) {
+            outputs[0] = outputs[0] + 1;
+            invalid_outputs[0] = u128::MAX;
+        }
+        invalid_bundles.push(miden::ProofBundle::new(
+            *program.hash(),
+            &pub_inputs,
+            &invalid_outputs,
+            proof.clone(),
+        ));
+        bundles.push(miden::ProofBundle::new(
+            *program.hash(),
+            &pub_inputs,
+            &outputs,
+            proof,
+        ));
+    }
+
+    match fail_idx {
+        Some(idx) => {
+            for bundles in [bundles, invalid_bundles] {
+                let failures = miden::verify_each(bundles).unwrap_err();
+                assert_eq!(
+                    vec![idx],
+                    failures.iter().map(|&(i, _)| i).collect::<Vec<_>>()
+                );
+            }
+        }
+        None => assert!(miden::verify_each(bundles).is_ok()),
+    }
+}
+
+#[cfg(test)]
+fn get_test_options() -> ProofOptions {
+    ProofOptions::new(
+        32,
+        8,
+        0,
+        miden::HashFunction::Blake3_256,
+        miden::FieldExtension::None,
+        8,
+        256,
+    )
+}
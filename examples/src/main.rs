@@ -1,7 +1,10 @@
 use examples::{Example, ExampleOptions, ExampleType};
 use log::debug;
-use miden::StarkProof;
-use std::{io::Write, time::Instant};
+use miden::{Program, ProgramInputs, StarkProof, TraceMetadata};
+use std::{
+    io::Write,
+    time::{Duration, Instant},
+};
 use structopt::StructOpt;
 
 fn main() {
@@ -28,6 +31,10 @@ fn main() {
         ExampleType::Merkle { tree_depth } => examples::merkle::get_example(tree_depth),
         #[cfg(feature = "std")]
         ExampleType::Range { num_values } => examples::range::get_example(num_values),
+        #[cfg(feature = "std")]
+        ExampleType::Compile { file } => return compile_program(&file),
+        #[cfg(feature = "std")]
+        ExampleType::Corpus { dir } => return run_corpus(&dir),
     };
 
     let Example {
@@ -40,6 +47,12 @@ fn main() {
     #[cfg(feature = "std")]
     debug!("--------------------------------");
 
+    // if requested, benchmark execution of the program instead of proving it
+    if let Some(iterations) = options.bench {
+        bench_program(&program, &inputs, iterations);
+        return;
+    }
+
     // execute the program and generate the proof of execution
     #[cfg(feature = "std")]
     let now = Instant::now();
@@ -75,3 +88,108 @@ fn main() {
         Err(msg) => debug!("Failed to verify execution: {}", msg),
     }
 }
+
+// COMPILATION
+// ================================================================================================
+
+/// Compiles the program in the specified file and prints out its hash; exits the process with
+/// a non-zero status if the file could not be read or the program failed to compile.
+#[cfg(feature = "std")]
+fn compile_program(file: &std::path::Path) {
+    let source = match std::fs::read_to_string(file) {
+        Ok(source) => source,
+        Err(err) => {
+            debug!("Failed to read {}: {}", file.display(), err);
+            std::process::exit(1);
+        }
+    };
+
+    let now = Instant::now();
+    match miden::assembly::compile(&source) {
+        Ok(program) => {
+            debug!(
+                "Compiled {} in {} ms",
+                file.display(),
+                now.elapsed().as_millis()
+            );
+            debug!("Program hash: {}", hex::encode(program.hash()));
+        }
+        Err(err) => {
+            debug!("Failed to compile {}: {}", file.display(), err);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Runs all programs in the specified corpus directory and prints out programs which failed.
+#[cfg(feature = "std")]
+fn run_corpus(dir: &std::path::Path) {
+    let report = examples::corpus::run_corpus(dir)
+        .unwrap_or_else(|err| panic!("failed to read corpus {}: {}", dir.display(), err));
+
+    for failure in report.failures.iter() {
+        debug!("{}", failure);
+    }
+    debug!(
+        "{} of {} programs passed",
+        report.num_programs - report.failures.len(),
+        report.num_programs
+    );
+    if !report.failures.is_empty() {
+        std::process::exit(1);
+    }
+}
+
+// BENCHMARKING
+// ================================================================================================
+
+/// Executes the program (without generating a proof) the specified number of times and prints
+/// timing statistics for building the execution trace; the number of cycles is the number of
+/// steps executed by the program (i.e., not including steps added to pad the trace).
+fn bench_program(program: &Program, inputs: &ProgramInputs, iterations: usize) {
+    assert!(
+        iterations > 0,
+        "number of iterations must be greater than 0"
+    );
+
+    let mut timings = Vec::with_capacity(iterations);
+    let mut num_cycles = 0;
+    for _ in 0..iterations {
+        let now = Instant::now();
+        let trace = processor::execute(program, inputs);
+        timings.push(now.elapsed());
+        num_cycles = TraceMetadata::from_trace_info(&trace.get_info()).num_steps;
+    }
+
+    timings.sort();
+    let mean = timings.iter().sum::<Duration>().div_f64(iterations as f64);
+    let median = timings[iterations / 2];
+
+    debug!("Executed program {} times", iterations);
+    debug!("Cycles: {}", num_cycles);
+    debug!("Mean execution time: {:.3} ms", mean.as_secs_f64() * 1000.0);
+    debug!(
+        "Median execution time: {:.3} ms",
+        median.as_secs_f64() * 1000.0
+    );
+    debug!(
+        "Cycles per second: {:.0}",
+        num_cycles as f64 / mean.as_secs_f64()
+    );
+    match get_peak_memory() {
+        Some(kb) => debug!("Peak memory: {} KB", kb),
+        None => debug!("Peak memory: unavailable"),
+    }
+}
+
+/// Returns peak resident set size of the current process in KB; this is available only on Linux.
+fn get_peak_memory() -> Option<usize> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    status
+        .lines()
+        .find(|line| line.starts_with("VmHWM:"))?
+        .split_whitespace()
+        .nth(1)?
+        .parse()
+        .ok()
+}
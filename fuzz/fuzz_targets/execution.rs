@@ -0,0 +1,14 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+// Generates a random program which is guaranteed to execute successfully, and makes sure that
+// the resulting execution trace satisfies all AIR constraints.
+fuzz_target!(|data: &[u8]| {
+    let (program, inputs) = miden::fuzzing::generate_program(data);
+    if let Err(failure) = miden::check_constraints(&program, &inputs, 0) {
+        panic!(
+            "execution trace does not satisfy AIR constraints: {}",
+            failure
+        );
+    }
+});
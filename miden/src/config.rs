@@ -0,0 +1,55 @@
+use crate::{assembly::AssemblyOptions, ExecutionOptions, ProofOptions};
+
+// VM CONFIG
+// ================================================================================================
+
+/// Options for every stage of running a program on the VM: compiling the program, building an
+/// execution trace, and generating a proof of execution.
+///
+/// This groups [AssemblyOptions], [ExecutionOptions], and [ProofOptions] so that they can be
+/// configured in one place; each set of options can still be used on its own.
+#[derive(Default)]
+pub struct VmConfig {
+    assembly: AssemblyOptions,
+    execution: ExecutionOptions,
+    proof: ProofOptions,
+}
+
+impl VmConfig {
+    /// Returns a config with default assembly and execution options, and the specified proof
+    /// options.
+    pub fn new(proof: ProofOptions) -> Self {
+        VmConfig {
+            assembly: AssemblyOptions::default(),
+            execution: ExecutionOptions::default(),
+            proof,
+        }
+    }
+
+    /// Returns this config modified with the specified assembly options.
+    pub fn with_assembly_options(mut self, options: AssemblyOptions) -> Self {
+        self.assembly = options;
+        self
+    }
+
+    /// Returns this config modified with the specified execution options.
+    pub fn with_execution_options(mut self, options: ExecutionOptions) -> Self {
+        self.execution = options;
+        self
+    }
+
+    /// Returns options used when compiling programs.
+    pub fn assembly_options(&self) -> &AssemblyOptions {
+        &self.assembly
+    }
+
+    /// Returns options used when building execution traces.
+    pub fn execution_options(&self) -> &ExecutionOptions {
+        &self.execution
+    }
+
+    /// Returns options used when generating proofs.
+    pub fn proof_options(&self) -> &ProofOptions {
+        &self.proof
+    }
+}
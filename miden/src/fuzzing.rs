@@ -0,0 +1,154 @@
+use super::{assembly, Program, ProgramInputs};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Stack depth which generated programs never exceed; this leaves enough room for instructions
+/// which push several values onto the stack at once.
+const MAX_GENERATED_DEPTH: usize = 24;
+
+/// Maximum nesting depth of generated control flow blocks.
+const MAX_NESTING_DEPTH: usize = 3;
+
+/// Maximum number of instructions in a single generated block body.
+const MAX_BODY_LENGTH: usize = 32;
+
+// PROGRAM GENERATOR
+// ================================================================================================
+
+/// Builds a program from arbitrary bytes (e.g. provided by a fuzzer) such that the program is
+/// guaranteed to execute successfully; returns the program together with its inputs.
+///
+/// Bytes are consumed one at a time to select instructions and their parameters. The generator
+/// tracks stack depth so that no instruction underflows or overflows the stack, and always
+/// precedes conditional blocks with a binary value. Every nested block leaves the stack at the
+/// same depth at which it started. Generation stops when the bytes are exhausted.
+pub fn generate_program(data: &[u8]) -> (Program, ProgramInputs) {
+    let mut generator = Generator {
+        data,
+        position: 0,
+        source: String::from("begin"),
+    };
+
+    let depth = generator.append_body(0, 0);
+    generator.append_restore(depth, 0);
+    generator.source.push_str(" end");
+
+    let program = assembly::compile(&generator.source)
+        .unwrap_or_else(|err| panic!("generated program failed to compile: {}", err));
+    (program, ProgramInputs::none())
+}
+
+struct Generator<'a> {
+    data: &'a [u8],
+    position: usize,
+    source: String,
+}
+
+impl<'a> Generator<'a> {
+    /// Returns the next byte of the input, or None if all bytes have been consumed.
+    fn next_byte(&mut self) -> Option<u8> {
+        let byte = self.data.get(self.position).copied();
+        self.position += 1;
+        byte
+    }
+
+    fn append(&mut self, instruction: &str) {
+        self.source.push(' ');
+        self.source.push_str(instruction);
+    }
+
+    /// Appends a sequence of instructions starting at the specified stack depth and returns
+    /// the stack depth after these instructions are executed. The body always contains at
+    /// least one instruction.
+    fn append_body(&mut self, mut depth: usize, nesting: usize) -> usize {
+        self.append("noop");
+        for _ in 0..MAX_BODY_LENGTH {
+            let byte = match self.next_byte() {
+                Some(byte) => byte,
+                None => break,
+            };
+            depth = match byte % 16 {
+                0 | 1 => self.append_push(depth),
+                2 => self.append_simple(depth, "dup", 1, 1, 1),
+                3 => self.append_simple(depth, "dup.2", 2, 2, 2),
+                4 => self.append_simple(depth, "swap", 2, 0, 0),
+                5 => self.append_simple(depth, "swap.2", 4, 0, 0),
+                6 => self.append_simple(depth, "roll.4", 4, 0, 0),
+                7 => self.append_simple(depth, "drop", 1, 0, 1),
+                8 => self.append_simple(depth, "add", 2, 0, 1),
+                9 => self.append_simple(depth, "mul", 2, 0, 1),
+                10 => self.append_simple(depth, "neg", 1, 0, 0),
+                11 => self.append_simple(depth, "eq", 2, 0, 1),
+                12 => self.append_simple(depth, "pad.2", 0, 2, 0),
+                13 | 14 => self.append_if_else(depth, nesting),
+                _ => self.append_repeat(depth, nesting),
+            };
+        }
+        depth
+    }
+
+    /// Appends an instruction which requires at least `min_depth` items on the stack, pushes
+    /// `pushed` items onto the stack, and removes `popped` items from it. If the instruction
+    /// cannot be executed at the current depth, nothing is appended.
+    fn append_simple(
+        &mut self,
+        depth: usize,
+        instruction: &str,
+        min_depth: usize,
+        pushed: usize,
+        popped: usize,
+    ) -> usize {
+        if depth < min_depth || depth + pushed > MAX_GENERATED_DEPTH {
+            return depth;
+        }
+        self.append(instruction);
+        depth + pushed - popped
+    }
+
+    fn append_push(&mut self, depth: usize) -> usize {
+        if depth == MAX_GENERATED_DEPTH {
+            return depth;
+        }
+        let value = self.next_byte().unwrap_or(0);
+        self.append(&format!("push.{}", value));
+        depth + 1
+    }
+
+    fn append_if_else(&mut self, depth: usize, nesting: usize) -> usize {
+        if nesting == MAX_NESTING_DEPTH || depth == MAX_GENERATED_DEPTH {
+            return depth;
+        }
+        let condition = self.next_byte().unwrap_or(0) & 1;
+        self.append(&format!("push.{} if.true", condition));
+        let t_depth = self.append_body(depth, nesting + 1);
+        self.append_restore(t_depth, depth);
+        self.append("else");
+        let f_depth = self.append_body(depth, nesting + 1);
+        self.append_restore(f_depth, depth);
+        self.append("end");
+        depth
+    }
+
+    fn append_repeat(&mut self, depth: usize, nesting: usize) -> usize {
+        if nesting == MAX_NESTING_DEPTH {
+            return depth;
+        }
+        let num_iterations = 2 + self.next_byte().unwrap_or(0) % 3;
+        self.append(&format!("repeat.{}", num_iterations));
+        let body_depth = self.append_body(depth, nesting + 1);
+        self.append_restore(body_depth, depth);
+        self.append("end");
+        depth
+    }
+
+    /// Appends instructions which bring the stack from the `current` depth to the `target` depth.
+    fn append_restore(&mut self, current: usize, target: usize) {
+        for _ in target..current {
+            self.append("drop");
+        }
+        for _ in current..target {
+            self.append("pad");
+        }
+    }
+}
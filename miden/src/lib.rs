@@ -1,22 +1,40 @@
-use air::{ProcessorAir, PublicInputs, TraceMetadata, TraceState, MAX_OUTPUTS, MIN_TRACE_LENGTH};
-use core::{convert::TryInto, ops::Deref};
+use air::{ProcessorAir, TraceState, MAX_OUTPUTS, MIN_TRACE_LENGTH};
+use core::{
+    cmp,
+    convert::TryInto,
+    ops::{Deref, Range},
+};
 #[cfg(feature = "std")]
 use log::debug;
-use prover::{ExecutionTrace, ProverError, Serializable};
+use prover::{ProverError, Serializable};
 #[cfg(feature = "std")]
 use std::time::Instant;
 
+mod config;
+pub use config::VmConfig;
+
+#[cfg(any(test, feature = "fuzzing"))]
+pub mod fuzzing;
+
 #[cfg(test)]
 mod tests;
 
 // EXPORTS
 // ================================================================================================
 
-pub use air::{FieldExtension, HashFunction, ProofOptions};
+pub use air::{
+    ConstraintComponent, ConstraintCoverage, ConstraintFailure, FieldExtension, HashFunction,
+    ProofOptions, TraceMetadata,
+};
 pub use assembly;
-pub use processor::{BaseElement, FieldElement, Program, ProgramInputs, StarkField};
-pub use prover::StarkProof;
-pub use verifier::{verify, VerifierError};
+pub use processor::{
+    BaseElement, ExecutionOptions, FieldElement, InputError, Program, ProgramInputs, StarkField,
+};
+pub use prover::{ExecutionTrace, StarkProof};
+pub use verifier::{
+    verify, verify_each, verify_public_inputs, ProofBundle, PublicInputs, PublicInputsError,
+    VerifierError,
+};
 
 // EXECUTOR
 // ================================================================================================
@@ -30,6 +48,42 @@ pub fn execute(
     inputs: &ProgramInputs,
     num_outputs: usize,
     options: &ProofOptions,
+) -> Result<(Vec<u128>, StarkProof), ProverError> {
+    execute_and_prove(
+        program,
+        inputs,
+        num_outputs,
+        &ExecutionOptions::default(),
+        options,
+    )
+}
+
+/// Executes the specified `program` and returns the result together with a STARK-based proof of
+/// execution; the execution trace and the proof are built according to the specified `config`.
+///
+/// * `inputs` specifies the initial stack state and provides secret input tapes;
+/// * `num_outputs` specifies the number of elements from the top of the stack to be returned;
+pub fn execute_with_config(
+    program: &Program,
+    inputs: &ProgramInputs,
+    num_outputs: usize,
+    config: &VmConfig,
+) -> Result<(Vec<u128>, StarkProof), ProverError> {
+    execute_and_prove(
+        program,
+        inputs,
+        num_outputs,
+        config.execution_options(),
+        config.proof_options(),
+    )
+}
+
+fn execute_and_prove(
+    program: &Program,
+    inputs: &ProgramInputs,
+    num_outputs: usize,
+    execution_options: &ExecutionOptions,
+    proof_options: &ProofOptions,
 ) -> Result<(Vec<u128>, StarkProof), ProverError> {
     assert!(
         num_outputs <= MAX_OUTPUTS,
@@ -41,7 +95,7 @@ pub fn execute(
     // execute the program to create an execution trace
     #[cfg(feature = "std")]
     let now = Instant::now();
-    let trace = processor::execute(program, inputs);
+    let trace = processor::execute_with_options(program, inputs, execution_options);
     #[cfg(feature = "std")]
     debug!(
         "Generated execution trace of {} registers and {} steps in {} ms",
@@ -82,11 +136,149 @@ pub fn execute(
         .map(|&v| v.as_int())
         .collect::<Vec<_>>();
     let pub_inputs = PublicInputs::new(program_hash, &inputs, &outputs);
-    let proof = prover::prove::<ProcessorAir>(trace, pub_inputs, options.deref().clone())?;
+    let proof = prover::prove::<ProcessorAir>(trace, pub_inputs, proof_options.deref().clone())?;
 
     Ok((outputs, proof))
 }
 
+// CONSTRAINT CHECKER
+// ================================================================================================
+
+/// Executes the specified `program` and checks the resulting execution trace against all AIR
+/// constraints without generating a proof.
+///
+/// Returns the outputs of the program if all constraints are satisfied; otherwise, returns a
+/// description of the first constraint which failed together with the trace values it was
+/// evaluated against. This is useful for debugging divergences between the processor and the AIR.
+pub fn check_constraints(
+    program: &Program,
+    inputs: &ProgramInputs,
+    num_outputs: usize,
+) -> Result<Vec<u128>, ConstraintFailure> {
+    assert!(
+        num_outputs <= MAX_OUTPUTS,
+        "cannot produce more than {} outputs, but requested {}",
+        MAX_OUTPUTS,
+        num_outputs
+    );
+
+    let trace = processor::execute(program, inputs);
+    let outputs = get_last_state(&trace).user_stack()[..num_outputs]
+        .iter()
+        .map(|&v| v.as_int())
+        .collect::<Vec<_>>();
+
+    // use the hash of the program rather than the hash from the trace so that a mismatch
+    // between the two is reported as a failed assertion
+    let inputs = inputs
+        .public_inputs()
+        .iter()
+        .map(|&v| v.as_int())
+        .collect::<Vec<_>>();
+    let pub_inputs = PublicInputs::new(*program.hash(), &inputs, &outputs);
+
+    air::check_trace(trace.get_info(), pub_inputs, |step, row| {
+        trace.read_row_into(step, row)
+    })?;
+
+    Ok(outputs)
+}
+
+// TRACE INSPECTION
+// ================================================================================================
+
+/// Returns the specified steps of an execution trace formatted one step per line; each register
+/// value is labeled with the name of the register (see [TraceMetadata::register_name()]).
+///
+/// Steps beyond the end of the trace are ignored.
+pub fn format_rows(trace: &ExecutionTrace<BaseElement>, steps: Range<usize>) -> String {
+    let meta = TraceMetadata::from_trace_info(&trace.get_info());
+    let names = (0..trace.width())
+        .map(|i| meta.register_name(i))
+        .collect::<Vec<_>>();
+
+    let mut result = String::new();
+    let mut row = vec![BaseElement::ZERO; trace.width()];
+    for step in steps.start..cmp::min(steps.end, trace.length()) {
+        trace.read_row_into(step, &mut row);
+        let values = names
+            .iter()
+            .zip(row.iter())
+            .map(|(name, value)| format!("{}={}", name, value.as_int()))
+            .collect::<Vec<_>>();
+        result.push_str(&format!("[{:>4}] {}\n", step, values.join(" ")));
+    }
+    result
+}
+
+/// Describes the first step at which values of a register differ between two execution traces.
+///
+/// A value is `None` if the register or the step does not exist in the corresponding trace
+/// (e.g. because the traces have different widths or lengths).
+#[derive(Clone, Debug, PartialEq)]
+pub struct RegisterDiff {
+    pub register: usize,
+    pub name: String,
+    pub step: usize,
+    pub left: Option<BaseElement>,
+    pub right: Option<BaseElement>,
+}
+
+/// Compares two execution traces register by register, and returns the first differing step for
+/// every register whose values differ; registers with identical values are not reported.
+///
+/// This is useful for validating that changes to trace generation (e.g. refactorings of the
+/// processor) do not change the trace of a program.
+pub fn diff_traces(
+    left: &ExecutionTrace<BaseElement>,
+    right: &ExecutionTrace<BaseElement>,
+) -> Vec<RegisterDiff> {
+    let left_meta = TraceMetadata::from_trace_info(&left.get_info());
+    let right_meta = TraceMetadata::from_trace_info(&right.get_info());
+    let width = cmp::max(left.width(), right.width());
+    let length = cmp::max(left.length(), right.length());
+
+    let mut left_row = vec![BaseElement::ZERO; left.width()];
+    let mut right_row = vec![BaseElement::ZERO; right.width()];
+    let mut result: Vec<RegisterDiff> = Vec::new();
+    let mut is_reported = vec![false; width];
+    for step in 0..length {
+        if step < left.length() {
+            left.read_row_into(step, &mut left_row);
+        }
+        if step < right.length() {
+            right.read_row_into(step, &mut right_row);
+        }
+
+        for register in 0..width {
+            if is_reported[register] {
+                continue;
+            }
+
+            let left_value = left_row.get(register).filter(|_| step < left.length());
+            let right_value = right_row.get(register).filter(|_| step < right.length());
+            if left_value != right_value {
+                let name = if register < left.width() {
+                    left_meta.register_name(register)
+                } else {
+                    right_meta.register_name(register)
+                };
+                result.push(RegisterDiff {
+                    register,
+                    name,
+                    step,
+                    left: left_value.copied(),
+                    right: right_value.copied(),
+                });
+                is_reported[register] = true;
+            }
+        }
+    }
+
+    result.sort_by_key(|diff| diff.register);
+    result
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
@@ -99,18 +291,3 @@ fn get_last_state(trace: &ExecutionTrace<BaseElement>) -> TraceState<BaseElement
 
     TraceState::from_slice(meta.ctx_depth, meta.loop_depth, meta.stack_depth, &last_row)
 }
-
-/// Prints out an execution trace.
-#[allow(unused)]
-fn print_trace(trace: &ExecutionTrace<BaseElement>, _multiples_of: usize) {
-    let trace_width = trace.width();
-    let meta = TraceMetadata::from_trace_info(&trace.get_info());
-
-    let mut state = vec![BaseElement::ZERO; trace_width];
-    for i in 0..trace.length() {
-        trace.read_row_into(i, &mut state);
-        let state =
-            TraceState::from_slice(meta.ctx_depth, meta.loop_depth, meta.stack_depth, &state);
-        println!("{:?}", state);
-    }
-}
@@ -26,6 +26,132 @@ fn execute_span() {
     assert_eq!([7, 15, 0, 0, 0, 0, 0, 0].to_elements(), state.user_stack());
 }
 
+#[test]
+fn trace_metadata() {
+    let program = assembly::compile("begin add push.5 mul push.7 end").unwrap();
+    let inputs = ProgramInputs::from_public(&[1, 2]);
+
+    let trace = processor::execute(&program, &inputs);
+    let meta = TraceMetadata::from_trace_info(&trace.get_info());
+
+    assert_eq!(trace.width(), meta.trace_width());
+    assert_eq!(15, meta.decoder_width());
+    assert_eq!(15..15, meta.ctx_stack_range());
+    assert_eq!(15..15, meta.loop_stack_range());
+    assert_eq!(15..17, meta.user_stack_range());
+    assert_eq!(46, meta.op_count);
+    assert_eq!(48, meta.num_steps);
+    assert_eq!(16, meta.padding_length());
+
+    assert_eq!("op_counter", meta.register_name(0));
+    assert_eq!("op_sponge_3", meta.register_name(4));
+    assert_eq!("cf_op_bit_0", meta.register_name(5));
+    assert_eq!("hd_op_bit_1", meta.register_name(14));
+    assert_eq!("user_stack_1", meta.register_name(16));
+}
+
+#[test]
+fn format_rows() {
+    let program = assembly::compile("begin add push.5 mul push.7 end").unwrap();
+    let inputs = ProgramInputs::from_public(&[1, 2]);
+    let trace = processor::execute(&program, &inputs);
+
+    let rows = crate::format_rows(&trace, 0..2);
+    let lines = rows.lines().collect::<Vec<_>>();
+    assert_eq!(2, lines.len());
+    assert!(lines[0].starts_with("[   0] op_counter=0 "));
+    assert!(lines[1].ends_with(" user_stack_0=1 user_stack_1=2"));
+
+    // steps beyond the end of the trace are ignored
+    let rows = crate::format_rows(&trace, 62..100);
+    assert_eq!(2, rows.lines().count());
+    assert!(rows.starts_with("[  62] "));
+    assert!(crate::format_rows(&trace, 100..200).is_empty());
+}
+
+#[test]
+fn diff_traces() {
+    let inputs = ProgramInputs::from_public(&[3]);
+    let program1 = assembly::compile("begin push.1 add end").unwrap();
+    let program2 = assembly::compile("begin push.2 add end").unwrap();
+
+    let trace1 = processor::execute(&program1, &inputs);
+    let trace2 = processor::execute(&program1, &inputs);
+    assert!(crate::diff_traces(&trace1, &trace2).is_empty());
+
+    let trace2 = processor::execute(&program2, &inputs);
+    let diffs = crate::diff_traces(&trace1, &trace2);
+    let diff = diffs.iter().find(|d| d.name == "user_stack_0").unwrap();
+    assert_eq!(9, diff.step);
+    assert_eq!(Some(BaseElement::new(1)), diff.left);
+    assert_eq!(Some(BaseElement::new(2)), diff.right);
+    assert!(diffs.iter().all(|d| d.name != "op_counter"));
+}
+
+#[test]
+fn execute_with_expected_cycles() {
+    let program = assembly::compile("begin add push.5 mul push.7 end").unwrap();
+    let inputs = ProgramInputs::from_public(&[1, 2]);
+
+    // the trace must be the same regardless of whether the hint is too small or too large
+    let trace = processor::execute(&program, &inputs);
+    for num_cycles in [16, 100, 1000].iter() {
+        let options = processor::ExecutionOptions::new().with_expected_cycles(*num_cycles);
+        let other = processor::execute_with_options(&program, &inputs, &options);
+        assert!(crate::diff_traces(&trace, &other).is_empty());
+    }
+}
+
+#[test]
+fn execute_with_max_cycles() {
+    let program = assembly::compile("begin add push.5 mul push.7 end").unwrap();
+    let inputs = ProgramInputs::from_public(&[1, 2]);
+
+    // the limit does not affect the trace of a program which runs within it
+    let trace = processor::execute(&program, &inputs);
+    let estimate = processor::estimate_trace(&program, &inputs).unwrap();
+    let options = processor::ExecutionOptions::new().with_max_cycles(estimate.num_steps as usize);
+    let other = processor::execute_with_options(&program, &inputs, &options);
+    assert!(crate::diff_traces(&trace, &other).is_empty());
+}
+
+#[test]
+#[should_panic(expected = "execution exceeded the step limit of 1024 steps")]
+fn execute_with_max_cycles_exceeded() {
+    let program =
+        assembly::compile("begin push.1 while.true push.1 push.0 swap drop end end").unwrap();
+    let options = processor::ExecutionOptions::new().with_max_cycles(1024);
+    processor::execute_with_options(&program, &ProgramInputs::none(), &options);
+}
+
+#[test]
+fn execute_batch() {
+    let batch = vec![
+        (
+            assembly::compile("begin add push.5 mul push.7 end").unwrap(),
+            ProgramInputs::from_public(&[1, 2]),
+        ),
+        (
+            assembly::compile("begin push.0 assert end").unwrap(),
+            ProgramInputs::none(),
+        ),
+        (
+            assembly::compile("begin mul read while.true dup mul read end end").unwrap(),
+            ProgramInputs::new(&[5, 3], &[1, 1, 0], &[]),
+        ),
+    ];
+
+    let results = processor::execute_batch(&batch);
+    assert_eq!(batch.len(), results.len());
+    assert!(results[1].is_err());
+    for i in [0, 2].iter() {
+        let (program, inputs) = &batch[*i];
+        let trace = processor::execute(program, inputs);
+        let batch_trace = results[*i].as_ref().unwrap();
+        assert!(crate::diff_traces(&trace, batch_trace).is_empty());
+    }
+}
+
 #[test]
 fn execute_block() {
     let program = assembly::compile("begin add block push.5 mul push.7 end end").unwrap();
@@ -153,6 +279,756 @@ fn execute_loop() {
     );
 }
 
+#[test]
+fn execute_clk() {
+    let program = assembly::compile("begin clk push.3 clk end").unwrap();
+    let inputs = ProgramInputs::none();
+
+    let trace = processor::execute(&program, &inputs);
+    let state = get_trace_state(&trace, trace.length() - 1);
+
+    // begin and the first clk are the 1st and 2nd operations; push is aligned on the 8th step,
+    // so the second clk is the 10th operation
+    assert_eq!([10, 3, 2, 0, 0, 0, 0, 0].to_elements(), state.user_stack());
+}
+
+#[test]
+fn execute_sdepth() {
+    let program = assembly::compile("begin sdepth push.3 sdepth add sdepth end").unwrap();
+    let inputs = ProgramInputs::from_public(&[1, 2]);
+
+    let trace = processor::execute(&program, &inputs);
+    let state = get_trace_state(&trace, trace.length() - 1);
+    assert_eq!([4, 7, 2, 1, 2, 0, 0, 0].to_elements(), state.user_stack());
+}
+
+#[test]
+fn execute_with_stack_assertions() {
+    let program =
+        assembly::compile("begin push.3 push.5 assert_stack_top.5.3 add assert_stack_top.8 end")
+            .unwrap();
+    let inputs = ProgramInputs::none();
+
+    let trace = processor::execute(&program, &inputs);
+    let state = get_trace_state(&trace, trace.length() - 1);
+    assert_eq!([8, 0, 0, 0, 0, 0, 0, 0].to_elements(), state.user_stack());
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "stack assertion failed")]
+fn execute_with_stack_assertions_fail() {
+    let program = assembly::compile("begin push.3 push.5 add assert_stack_top.9 end").unwrap();
+    processor::execute(&program, &ProgramInputs::none());
+}
+
+#[test]
+fn execute_with_signature() {
+    let program = assembly::compile("begin.inputs.2.outputs.1 add push.5 mul end").unwrap();
+    let inputs = ProgramInputs::from_public(&[1, 2]);
+
+    let trace = processor::execute(&program, &inputs);
+    let state = get_trace_state(&trace, trace.length() - 1);
+    assert_eq!(BaseElement::new(15), state.user_stack()[0]);
+    assert_eq!(
+        Ok(vec![BaseElement::new(15)]),
+        processor::execute_fast(&program, &inputs, 1)
+    );
+}
+
+#[test]
+#[should_panic(expected = "program declares 2 public inputs, but received 3")]
+fn execute_with_signature_input_mismatch() {
+    let program = assembly::compile("begin.inputs.2.outputs.1 add end").unwrap();
+    let inputs = ProgramInputs::from_public(&[1, 2, 3]);
+    processor::execute(&program, &inputs);
+}
+
+#[test]
+fn signature_errors() {
+    use processor::SignatureError;
+
+    let program = assembly::compile("begin.inputs.2.outputs.1 add end").unwrap();
+    let inputs = ProgramInputs::from_public(&[1, 2, 3]);
+    let expected = SignatureError::InputCountMismatch(2, 3);
+    assert_eq!(
+        Some(expected.clone()),
+        processor::try_execute(&program, &inputs).err()
+    );
+    assert_eq!(
+        Err(expected.clone()),
+        processor::execute_fast(&program, &inputs, 1)
+    );
+    assert_eq!(
+        Err(expected.clone()),
+        processor::estimate_trace(&program, &inputs)
+    );
+    assert_eq!(Err(expected), processor::profile_loops(&program, &inputs));
+
+    let program = assembly::compile("begin.inputs.2.outputs.1 dup add end").unwrap();
+    let inputs = ProgramInputs::from_public(&[1, 2]);
+    let expected = SignatureError::OutputCountMismatch(1, 2);
+    assert_eq!(
+        Some(expected.clone()),
+        processor::try_execute(&program, &inputs).err()
+    );
+    assert_eq!(
+        Err(expected.clone()),
+        processor::execute_fast(&program, &inputs, 1)
+    );
+    assert_eq!(
+        Err(expected.clone()),
+        processor::estimate_trace(&program, &inputs)
+    );
+    assert_eq!(Err(expected), processor::profile_loops(&program, &inputs));
+}
+
+#[test]
+fn execute_fast() {
+    let sources = [
+        "begin push.3 push.5 add clk end",
+        "begin push.1 while.true push.3 dup mul push.0 end clk end",
+        "begin push.1 if.true push.2 push.3 hash.2 else push.4 end clk end",
+    ];
+    for source in sources.iter() {
+        let program = assembly::compile(source).unwrap();
+        let inputs = ProgramInputs::none();
+
+        let trace = processor::execute(&program, &inputs);
+        let state = get_trace_state(&trace, trace.length() - 1);
+
+        let outputs = processor::execute_fast(&program, &inputs, 4).unwrap();
+        assert_eq!(&state.user_stack()[..4], &outputs[..]);
+    }
+}
+
+#[test]
+fn estimate_trace() {
+    let sources = [
+        "begin add push.5 mul push.7 end",
+        "begin mul read while.true dup mul read end end",
+        "begin push.1 if.true push.2 push.3 hash.2 else push.4 end clk end",
+    ];
+    for source in sources.iter() {
+        let program = assembly::compile(source).unwrap();
+        let inputs = ProgramInputs::new(&[5, 3], &[1, 1, 1, 0], &[]);
+
+        let trace = processor::execute(&program, &inputs);
+        let meta = TraceMetadata::from_trace_info(&trace.get_info());
+
+        let estimate = processor::estimate_trace(&program, &inputs).unwrap();
+        assert_eq!(trace.length() as u64, estimate.trace_length);
+        assert_eq!(meta.num_steps as u64, estimate.num_steps);
+    }
+}
+
+#[test]
+fn profile_loops() {
+    let program =
+        assembly::compile("begin read while.true read while.true read end read end end").unwrap();
+
+    // the outer loop executes 3 iterations; the inner loop is entered in the 1st and the 3rd
+    // iterations of the outer loop, and executes 3 and 1 iterations respectively
+    let inputs = ProgramInputs::new(&[], &[1, 1, 1, 1, 0, 1, 0, 1, 1, 0, 0], &[]);
+    let profile = processor::profile_loops(&program, &inputs).unwrap();
+
+    assert_eq!(2, profile.max_depth);
+    assert_eq!(2, profile.loops.len());
+
+    let outer = &profile.loops[0];
+    assert_eq!(
+        (1, 1, 3, 3),
+        (
+            outer.depth,
+            outer.entries,
+            outer.iterations,
+            outer.max_iterations
+        )
+    );
+
+    let inner = &profile.loops[1];
+    assert_eq!(
+        (2, 2, 4, 3),
+        (
+            inner.depth,
+            inner.entries,
+            inner.iterations,
+            inner.max_iterations
+        )
+    );
+
+    // loops which are never entered are not reported
+    let inputs = ProgramInputs::new(&[], &[0], &[]);
+    let profile = processor::profile_loops(&program, &inputs).unwrap();
+    assert_eq!(0, profile.max_depth);
+    assert!(profile.loops.is_empty());
+}
+
+#[test]
+fn execute_with_config() {
+    use crate::{ExecutionOptions, FieldExtension, HashFunction, ProofOptions, VmConfig};
+
+    let proof_options = ProofOptions::new(
+        32,
+        8,
+        0,
+        HashFunction::Blake3_256,
+        FieldExtension::None,
+        8,
+        256,
+    );
+    let config = VmConfig::new(proof_options)
+        .with_assembly_options(assembly::AssemblyOptions::new().with_max_loop_depth(1))
+        .with_execution_options(ExecutionOptions::new().with_expected_cycles(1024));
+
+    // assembly options from the config are applied when compiling programs
+    let source = "begin push.1 while.true push.1 while.true push.0 end push.0 end end";
+    assert!(assembly::compile_with_options(source, config.assembly_options()).is_err());
+
+    let source = "begin push.3 push.5 add end";
+    let program = assembly::compile_with_options(source, config.assembly_options()).unwrap();
+    let (outputs, proof) =
+        crate::execute_with_config(&program, &ProgramInputs::none(), 1, &config).unwrap();
+    assert_eq!(vec![8], outputs);
+    assert!(crate::verify(*program.hash(), &[], &outputs, proof).is_ok());
+}
+
+#[test]
+fn check_constraints() {
+    let program = assembly::compile("begin push.3 push.5 read add mul end").unwrap();
+    let inputs = ProgramInputs::new(&[], &[2], &[]);
+
+    let outputs = crate::check_constraints(&program, &inputs, 1).unwrap();
+    assert_eq!(vec![21], outputs);
+}
+
+#[test]
+fn check_constraints_invalid_outputs() {
+    use air::{check_trace, ConstraintFailure, PublicInputs};
+
+    let program = assembly::compile("begin push.3 push.5 add end").unwrap();
+    let trace = processor::execute(&program, &ProgramInputs::none());
+
+    // claim that the program output is 9 instead of 8
+    let pub_inputs = PublicInputs::new(*program.hash(), &[], &[9]);
+    let result = check_trace(trace.get_info(), pub_inputs, |step, row| {
+        trace.read_row_into(step, row)
+    });
+
+    match result {
+        Err(ConstraintFailure::Assertion {
+            step,
+            expected,
+            actual,
+            ..
+        }) => {
+            assert_eq!(trace.length() - 1, step);
+            assert_eq!(BaseElement::new(9), expected);
+            assert_eq!(BaseElement::new(8), actual);
+        }
+        _ => panic!("expected output assertion to fail"),
+    }
+}
+
+#[test]
+fn constraint_coverage() {
+    use air::{ConstraintCoverage, FlowOps, UserOps};
+
+    let mut coverage = ConstraintCoverage::new();
+    for source in ["begin push.3 push.5 add end", "begin push.3 push.5 mul end"].iter() {
+        let program = assembly::compile(source).unwrap();
+        let trace = processor::execute(&program, &ProgramInputs::none());
+        coverage.add_trace(&trace.get_info(), |step, row| {
+            trace.read_row_into(step, row)
+        });
+    }
+
+    let count = |op: UserOps| {
+        coverage
+            .user_ops()
+            .iter()
+            .find(|(o, _)| *o == op)
+            .unwrap()
+            .1
+    };
+    assert_eq!(4, count(UserOps::Push));
+    assert_eq!(1, count(UserOps::Add));
+    assert_eq!(1, count(UserOps::Mul));
+    assert_eq!(2, count(UserOps::Begin));
+
+    let unexercised = coverage.unexercised_user_ops();
+    assert!(unexercised.contains(&UserOps::RescR));
+    assert!(!unexercised.contains(&UserOps::Add));
+
+    let loop_count = coverage
+        .flow_ops()
+        .iter()
+        .find(|(o, _)| *o == FlowOps::Loop)
+        .unwrap()
+        .1;
+    assert_eq!(0, loop_count);
+}
+
+#[test]
+fn check_generated_programs() {
+    use crate::fuzzing::generate_program;
+
+    let inputs: [&[u8]; 4] = [
+        &[],
+        &[0, 7, 0, 9, 8, 2, 9, 11, 10],
+        &[13, 1, 0, 200, 3, 8, 14, 0, 15, 1, 1, 5, 2, 4],
+        &[
+            15, 2, 13, 0, 1, 3, 7, 15, 0, 12, 9, 12, 11, 6, 5, 3, 3, 3, 3,
+        ],
+    ];
+    for data in inputs.iter() {
+        let (program, inputs) = generate_program(data);
+        crate::check_constraints(&program, &inputs, 0).unwrap();
+    }
+}
+
+#[test]
+fn execute_mutations() {
+    use processor::mutation::{execute_mutations, MutationOutcome, OpCode};
+
+    let program = assembly::compile("begin push.1 dup mul add end").unwrap();
+    let inputs = ProgramInputs::from_public(&[1]);
+
+    let results = execute_mutations(&program, &inputs, 1)
+        .into_iter()
+        .map(|result| (result.op_code, result.outcome))
+        .collect::<Vec<_>>();
+
+    let expected = vec![
+        (OpCode::Push, MutationOutcome::OutputChanged),
+        (OpCode::Dup, MutationOutcome::ExecutionFailed),
+        (OpCode::Mul, MutationOutcome::Survived),
+        (OpCode::Add, MutationOutcome::OutputChanged),
+    ];
+    assert_eq!(expected, results);
+}
+
+#[test]
+fn execute_mutations_with_timeout() {
+    use processor::mutation::{execute_mutations, MutationOutcome, OpCode};
+
+    // the loop body leaves 0 on the stack, so the loop executes exactly once
+    let program =
+        assembly::compile("begin push.1 while.true push.1 push.0 swap drop end end").unwrap();
+    let inputs = ProgramInputs::none();
+
+    let results = execute_mutations(&program, &inputs, 1);
+    let outcome = |op_code: OpCode, occurrence: usize| {
+        results
+            .iter()
+            .filter(|result| result.op_code == op_code)
+            .nth(occurrence)
+            .map(|result| result.outcome)
+            .unwrap()
+    };
+
+    // pushing 2 instead of 1 in the loop body leaves 0 on the stack
+    assert_eq!(MutationOutcome::Survived, outcome(OpCode::Push, 1));
+    // pushing 1 instead of 0, or skipping the swap, leaves 1 on the stack and the loop never exits
+    assert_eq!(MutationOutcome::Timeout, outcome(OpCode::Push, 2));
+    assert_eq!(MutationOutcome::Timeout, outcome(OpCode::Swap, 0));
+}
+
+#[test]
+#[cfg(feature = "hooks")]
+fn execute_with_hooks() {
+    use processor::{blocks::ProgramBlock, mutation::OpCode, ExecutionHooks, ExecutionState};
+
+    /// A single hook invocation together with the execution state passed to it.
+    struct HookEvent {
+        name: &'static str,
+        op_code: Option<OpCode>,
+        step: usize,
+        op_counter: BaseElement,
+        stack_top: Vec<BaseElement>,
+    }
+
+    #[derive(Default)]
+    struct HookRecorder {
+        events: Vec<HookEvent>,
+    }
+
+    impl HookRecorder {
+        fn record(&mut self, name: &'static str, op_code: Option<OpCode>, state: &ExecutionState) {
+            self.events.push(HookEvent {
+                name,
+                op_code,
+                step: state.step(),
+                op_counter: state.op_counter(),
+                stack_top: state.stack_top(2),
+            });
+        }
+    }
+
+    impl ExecutionHooks for HookRecorder {
+        fn before_block(&mut self, _block: &ProgramBlock, state: &ExecutionState) {
+            self.record("before_block", None, state);
+        }
+
+        fn after_block(&mut self, _block: &ProgramBlock, state: &ExecutionState) {
+            self.record("after_block", None, state);
+        }
+
+        fn before_op(&mut self, op_code: OpCode, state: &ExecutionState) {
+            self.record("before_op", Some(op_code), state);
+        }
+
+        fn after_op(&mut self, op_code: OpCode, state: &ExecutionState) {
+            self.record("after_op", Some(op_code), state);
+        }
+    }
+
+    // the loop executes 2 iterations, and then the true branch of the if block is taken
+    let program = assembly::compile(
+        "begin read while.true dup mul read end read if.true add else mul end end",
+    )
+    .unwrap();
+    let inputs = ProgramInputs::new(&[3, 5], &[1, 1, 0, 1], &[]);
+
+    // hooks do not affect the execution trace
+    let mut hooks = HookRecorder::default();
+    let trace = processor::execute_with_hooks(&program, &inputs, &mut hooks);
+    assert!(crate::diff_traces(&processor::execute(&program, &inputs), &trace).is_empty());
+    let events = hooks.events;
+
+    // the first hook is invoked before any operation is executed, and the last hook is invoked
+    // after the program has produced its result
+    let first = &events[0];
+    assert_eq!("before_block", first.name);
+    assert_eq!(0, first.step);
+    assert_eq!(BaseElement::ZERO, first.op_counter);
+    assert_eq!([3, 5].to_elements(), first.stack_top);
+
+    let last = &events[events.len() - 1];
+    assert_eq!("after_block", last.name);
+    assert_eq!([86, 0].to_elements(), last.stack_top);
+
+    // blocks are properly nested, every operation is executed within a block, and hooks are
+    // invoked in the order of execution steps
+    let mut depth = 0;
+    for (i, event) in events.iter().enumerate() {
+        match event.name {
+            "before_block" => depth += 1,
+            "after_block" => depth -= 1,
+            _ => assert!(depth > 0),
+        }
+        assert!(depth >= 0);
+        if i > 0 {
+            assert!(event.step >= events[i - 1].step);
+        }
+    }
+    assert_eq!(0, depth);
+
+    // every before_op hook is immediately followed by after_op hook for the same operation, and
+    // executing the operation advances the step and the op counter by one
+    let mut op_codes = Vec::new();
+    for (before, after) in events.iter().zip(events.iter().skip(1)) {
+        if before.name == "before_op" {
+            assert_eq!("after_op", after.name);
+            assert_eq!(before.op_code, after.op_code);
+            assert_eq!(before.step + 1, after.step);
+            assert_eq!(before.op_counter + BaseElement::ONE, after.op_counter);
+            op_codes.push(before.op_code.unwrap());
+        }
+    }
+
+    // loop body is executed twice, and only the true branch of the if block is executed
+    let count = |op_code: OpCode| op_codes.iter().filter(|&&op| op == op_code).count();
+    assert_eq!(4, count(OpCode::Read));
+    assert_eq!(2, count(OpCode::Dup));
+    assert_eq!(2, count(OpCode::Mul));
+    assert_eq!(1, count(OpCode::Add));
+}
+
+#[test]
+#[cfg(feature = "hooks")]
+fn execute_with_memory_stats() {
+    use processor::{mutation::OpCode, ExecutionHooks, ExecutionState, MemoryStats};
+
+    #[derive(Default)]
+    struct StatsRecorder {
+        stats: Vec<(OpCode, MemoryStats)>,
+    }
+
+    impl ExecutionHooks for StatsRecorder {
+        fn after_op(&mut self, op_code: OpCode, state: &ExecutionState) {
+            self.stats.push((op_code, state.memory_stats()));
+        }
+    }
+
+    let program = assembly::compile("begin read read.ab push.1 end").unwrap();
+    let inputs = ProgramInputs::new(&[], &[1, 2], &[3]);
+
+    let mut recorder = StatsRecorder::default();
+    let trace = processor::execute_with_hooks(&program, &inputs, &mut recorder);
+    let stats_after = |op_code: OpCode| {
+        recorder
+            .stats
+            .iter()
+            .find(|(op, _)| *op == op_code)
+            .map(|(_, stats)| *stats)
+            .unwrap()
+    };
+
+    // tape values are consumed as they are read
+    assert_eq!([2, 1], stats_after(OpCode::Begin).tape_lengths);
+    assert_eq!([1, 1], stats_after(OpCode::Read).tape_lengths);
+    assert_eq!([0, 0], stats_after(OpCode::Read2).tape_lengths);
+
+    // max stack depth never decreases
+    assert_eq!(1, stats_after(OpCode::Read).max_stack_depth);
+    assert_eq!(3, stats_after(OpCode::Read2).max_stack_depth);
+    let last = recorder.stats.last().unwrap().1;
+    assert_eq!(4, last.max_stack_depth);
+
+    // register traces are allocated for at least as many steps as the final trace has; the
+    // decoder has 16 registers (including the outer-most context) and the stack has 8 registers
+    assert!(last.decoder_trace_cells >= 16 * trace.length());
+    assert!(last.stack_trace_cells >= 8 * trace.length());
+}
+
+#[test]
+#[cfg(all(feature = "hooks", feature = "std"))]
+fn execute_with_op_timings() {
+    use processor::{mutation::OpCode, OpTimings};
+
+    let program = assembly::compile("begin push.3 push.5 add dup mul end").unwrap();
+    let inputs = ProgramInputs::none();
+
+    let mut timings = OpTimings::new();
+    processor::execute_with_hooks(&program, &inputs, &mut timings);
+    let report = timings.report();
+
+    // every operation is counted exactly as many times as it was executed
+    let count = |op_code: OpCode| {
+        report
+            .iter()
+            .find(|timing| timing.op_code == op_code)
+            .map_or(0, |timing| timing.count)
+    };
+    assert_eq!(2, count(OpCode::Push));
+    assert_eq!(1, count(OpCode::Add));
+    assert_eq!(1, count(OpCode::Dup));
+    assert_eq!(1, count(OpCode::Mul));
+    assert_eq!(0, count(OpCode::Drop));
+
+    // operations are reported once each, starting with the one with the most time spent
+    for (i, timing) in report.iter().enumerate() {
+        assert!(report[i + 1..].iter().all(|t| t.op_code != timing.op_code));
+    }
+    for pair in report.windows(2) {
+        assert!(pair[0].total >= pair[1].total);
+    }
+
+    // the table contains a header and a row for every reported operation
+    assert_eq!(report.len() + 1, timings.to_string().lines().count());
+}
+
+#[test]
+#[cfg(feature = "logging")]
+fn execute_with_tracing() {
+    use std::sync::{Arc, Mutex};
+    use tracing::{
+        field::{Field, Visit},
+        span, Event, Metadata, Subscriber,
+    };
+
+    /// A span or an event together with the index of its parent span and all its fields.
+    struct Record {
+        name: &'static str,
+        parent: Option<usize>,
+        fields: Vec<(&'static str, String)>,
+    }
+
+    impl Record {
+        fn field(&self, name: &str) -> Option<&str> {
+            let field = self.fields.iter().find(|(n, _)| *n == name);
+            field.map(|(_, value)| value.as_str())
+        }
+    }
+
+    impl Visit for Record {
+        fn record_debug(&mut self, field: &Field, value: &dyn core::fmt::Debug) {
+            self.fields.push((field.name(), format!("{:?}", value)));
+        }
+    }
+
+    #[derive(Default)]
+    struct Recorded {
+        spans: Vec<Record>,
+        events: Vec<Record>,
+        entered: Vec<usize>,
+    }
+
+    /// Records all spans and events; span IDs are indexes of spans (starting with 1).
+    struct Recorder(Arc<Mutex<Recorded>>);
+
+    impl Recorder {
+        fn parent(
+            recorded: &Recorded,
+            parent: Option<&span::Id>,
+            contextual: bool,
+        ) -> Option<usize> {
+            match parent {
+                Some(id) => Some(id.into_u64() as usize - 1),
+                None if contextual => recorded.entered.last().copied(),
+                None => None,
+            }
+        }
+    }
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &span::Attributes<'_>) -> span::Id {
+            let mut recorded = self.0.lock().unwrap();
+            let mut record = Record {
+                name: span.metadata().name(),
+                parent: Self::parent(&recorded, span.parent(), span.is_contextual()),
+                fields: Vec::new(),
+            };
+            span.record(&mut record);
+            recorded.spans.push(record);
+            span::Id::from_u64(recorded.spans.len() as u64)
+        }
+
+        fn record(&self, span: &span::Id, values: &span::Record<'_>) {
+            let mut recorded = self.0.lock().unwrap();
+            values.record(&mut recorded.spans[span.into_u64() as usize - 1]);
+        }
+
+        fn record_follows_from(&self, _span: &span::Id, _follows: &span::Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let mut recorded = self.0.lock().unwrap();
+            let mut record = Record {
+                name: event.metadata().name(),
+                parent: Self::parent(&recorded, event.parent(), event.is_contextual()),
+                fields: Vec::new(),
+            };
+            event.record(&mut record);
+            recorded.events.push(record);
+        }
+
+        fn enter(&self, span: &span::Id) {
+            let mut recorded = self.0.lock().unwrap();
+            recorded.entered.push(span.into_u64() as usize - 1);
+        }
+
+        fn exit(&self, span: &span::Id) {
+            let mut recorded = self.0.lock().unwrap();
+            let index = span.into_u64() as usize - 1;
+            let position = recorded.entered.iter().rposition(|&i| i == index).unwrap();
+            recorded.entered.remove(position);
+        }
+    }
+
+    // the loop is nested in the true branch of the if block, and executes 2 iterations
+    let program =
+        assembly::compile("begin read if.true read while.true read end else push.3 end end")
+            .unwrap();
+    let inputs = ProgramInputs::new(&[], &[1, 1, 1, 0], &[]);
+
+    let recorded = Arc::new(Mutex::new(Recorded::default()));
+    tracing::subscriber::with_default(Recorder(recorded.clone()), || {
+        processor::execute(&program, &inputs);
+    });
+    let recorded = recorded.lock().unwrap();
+
+    // spans form a tree which mirrors the nesting of blocks; every span records the step at
+    // which its block was started and the hash of its block once the block was closed
+    let spans = &recorded.spans;
+    assert_eq!(
+        vec![("program", None), ("block", Some(0)), ("loop", Some(1))],
+        spans.iter().map(|s| (s.name, s.parent)).collect::<Vec<_>>()
+    );
+    assert_eq!(Some("0"), spans[0].field("clk"));
+    let clks = spans
+        .iter()
+        .map(|s| s.field("clk").unwrap().parse::<usize>().unwrap())
+        .collect::<Vec<_>>();
+    assert!(clks[0] < clks[1] && clks[1] < clks[2]);
+    assert!(spans.iter().all(|s| s.field("hash").is_some()));
+    assert!(recorded.entered.is_empty());
+
+    // tape reads are attributed to the innermost block executing at the time of the read
+    let reads = recorded
+        .events
+        .iter()
+        .filter(|e| e.field("message") == Some("read from tape A"))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![Some(0), Some(1), Some(2), Some(2)],
+        reads.iter().map(|e| e.parent).collect::<Vec<_>>()
+    );
+    assert!(reads.iter().all(|e| e.field("clk").is_some()));
+}
+
+#[test]
+fn encode_public_inputs() {
+    use crate::{PublicInputs, PublicInputsError};
+
+    let program = assembly::compile("begin add push.5 mul push.7 end").unwrap();
+    let pub_inputs = PublicInputs::new(*program.hash(), &[1, 2], &[7, 15]);
+
+    let bytes = pub_inputs.encode();
+    assert_eq!(1 + 32 + 1 + 2 * 16 + 1 + 2 * 16, bytes.len());
+
+    let decoded = PublicInputs::decode(&bytes).unwrap();
+    assert_eq!(pub_inputs, decoded);
+    assert_eq!(*program.hash(), decoded.program_hash());
+    assert_eq!(vec![1, 2], decoded.inputs());
+    assert_eq!(vec![7, 15], decoded.outputs());
+
+    // malformed encodings
+    let mut invalid = bytes.clone();
+    invalid[0] = 2;
+    assert_eq!(
+        Err(PublicInputsError::UnsupportedVersion(2)),
+        PublicInputs::decode(&invalid)
+    );
+    assert_eq!(
+        Err(PublicInputsError::UnexpectedEnd),
+        PublicInputs::decode(&bytes[..bytes.len() - 1])
+    );
+    let mut invalid = bytes.clone();
+    invalid[17..33].copy_from_slice(&u128::MAX.to_le_bytes());
+    assert_eq!(
+        Err(PublicInputsError::InvalidValue(u128::MAX)),
+        PublicInputs::decode(&invalid)
+    );
+    let mut invalid = bytes;
+    invalid.push(0);
+    assert_eq!(
+        Err(PublicInputsError::TrailingBytes(1)),
+        PublicInputs::decode(&invalid)
+    );
+
+    // invalid public inputs are reported as errors rather than panics
+    let hash = *program.hash();
+    assert_eq!(
+        Err(PublicInputsError::TooManyValues(9)),
+        PublicInputs::try_new(hash, &[0; 9], &[])
+    );
+    assert_eq!(
+        Err(PublicInputsError::InvalidValue(u128::MAX)),
+        PublicInputs::try_new(hash, &[1, u128::MAX], &[])
+    );
+    let mut invalid_hash = hash;
+    invalid_hash[16..].copy_from_slice(&u128::MAX.to_le_bytes());
+    assert_eq!(
+        Err(PublicInputsError::InvalidValue(u128::MAX)),
+        PublicInputs::try_new(invalid_hash, &[], &[])
+    );
+}
+
 fn get_trace_state(trace: &ExecutionTrace<BaseElement>, step: usize) -> TraceState<BaseElement> {
     let meta = TraceMetadata::from_trace_info(&trace.get_info());
     let mut row = vec![BaseElement::ZERO; trace.width()];
@@ -0,0 +1,96 @@
+use super::{execute, BaseElement, ExecutionTrace, Program, ProgramInputs};
+use core::fmt;
+use std::{
+    any::Any,
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+};
+
+// BATCH EXECUTION
+// ================================================================================================
+
+/// Executes every program in the `batch` against its inputs and returns the resulting execution
+/// traces in the same order as the programs appear in the batch.
+///
+/// Programs are executed concurrently by a pool of threads (one thread per available CPU core),
+/// with each thread picking up the next unexecuted program as soon as it is done with the
+/// previous one. A failed execution does not affect other programs in the batch: the failure is
+/// caught and returned in place of the trace; however, the panic message will still be printed
+/// by the default panic hook.
+pub fn execute_batch(
+    batch: &[(Program, ProgramInputs)],
+) -> Vec<Result<ExecutionTrace<BaseElement>, ExecutionFailure>> {
+    let num_threads = thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .min(batch.len());
+    let next_idx = AtomicUsize::new(0);
+    let results = Mutex::new((0..batch.len()).map(|_| None).collect::<Vec<_>>());
+
+    thread::scope(|scope| {
+        for _ in 0..num_threads {
+            scope.spawn(|| loop {
+                let idx = next_idx.fetch_add(1, Ordering::Relaxed);
+                if idx >= batch.len() {
+                    break;
+                }
+
+                let (program, inputs) = &batch[idx];
+                let result = catch_unwind(AssertUnwindSafe(|| execute(program, inputs)))
+                    .map_err(ExecutionFailure::from_panic);
+                results.lock().unwrap()[idx] = Some(result);
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|result| result.expect("program in the batch was not executed"))
+        .collect()
+}
+
+// EXECUTION FAILURE
+// ================================================================================================
+
+/// Describes why execution of a program in a batch failed.
+#[derive(Clone, PartialEq)]
+pub struct ExecutionFailure {
+    message: String,
+}
+
+impl ExecutionFailure {
+    /// Returns a failure describing the panic with the specified payload (e.g. as returned by
+    /// `std::panic::catch_unwind()`).
+    pub fn from_panic(payload: Box<dyn Any + Send>) -> Self {
+        let message = if let Some(message) = payload.downcast_ref::<&str>() {
+            message.to_string()
+        } else if let Some(message) = payload.downcast_ref::<String>() {
+            message.clone()
+        } else {
+            String::from("unknown error")
+        };
+        ExecutionFailure { message }
+    }
+
+    /// Returns the message with which the execution failed.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Debug for ExecutionFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl fmt::Display for ExecutionFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "execution failed: {}", self.message)
+    }
+}
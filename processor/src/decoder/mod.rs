@@ -1,14 +1,17 @@
 use crate::{
+    fast::STEP_LIMIT_EXCEEDED,
     op_sponge,
     opcodes::{FlowOps, UserOps},
     BaseElement, FieldElement, StarkField, BASE_CYCLE_LENGTH, MAX_CONTEXT_DEPTH, MAX_LOOP_DEPTH,
-    NUM_CF_OP_BITS, NUM_HD_OP_BITS, NUM_LD_OP_BITS, PUSH_OP_ALIGNMENT,
+    MIN_TRACE_LENGTH, NUM_CF_OP_BITS, NUM_HD_OP_BITS, NUM_LD_OP_BITS, PUSH_OP_ALIGNMENT,
 };
+use core::cmp;
 
 // TYPES AND INTERFACES
 // ================================================================================================
 pub struct Decoder {
     step: usize,
+    max_steps: usize,
 
     op_counter: Vec<BaseElement>,
     op_sponge_trace: [Vec<BaseElement>; op_sponge::STATE_WIDTH],
@@ -23,13 +26,54 @@ pub struct Decoder {
 
     loop_stack: Vec<Vec<BaseElement>>,
     loop_depth: usize,
+
+    /// Spans of all currently executing blocks, starting with the span of the whole program; a
+    /// span is entered when its block is started, and exited when the block is closed.
+    #[cfg(feature = "logging")]
+    spans: Vec<tracing::span::EnteredSpan>,
+}
+
+/// Operations through which the processor drives the decoder while executing program blocks.
+///
+/// [Decoder] builds register traces for all decoder registers; other implementations may keep
+/// track only of the information they need (e.g. to execute programs without building execution
+/// traces), but must advance steps and the op counter in exactly the same way.
+pub trait ProgramDecoder {
+    /// Returns value of the current step pointer.
+    fn current_step(&self) -> usize;
+
+    /// Returns the value of the op_counter register at the current step.
+    fn op_counter(&self) -> BaseElement;
+
+    /// Returns the number of field elements allocated for decoder register traces; this is 0
+    /// for decoders which do not build register traces.
+    fn num_trace_cells(&self) -> usize;
+
+    /// Initiates a new program block (Group or Switch).
+    fn start_block(&mut self);
+
+    /// Terminates a program block (Group, Switch, or Loop).
+    fn end_block(&mut self, sibling_hash: BaseElement, true_branch: bool);
+
+    /// Initiates a new Loop block
+    fn start_loop(&mut self, loop_image: BaseElement);
+
+    /// Prepares the decoder for the next iteration of a loop.
+    fn wrap_loop(&mut self);
+
+    /// Prepares the decoder for exiting a loop.
+    fn break_loop(&mut self);
+
+    /// Updates the decoder with the value of the specified operation.
+    fn decode_op(&mut self, op_code: UserOps, op_value: BaseElement);
 }
 
 // DECODER IMPLEMENTATION
 // ================================================================================================
 impl Decoder {
-    /// Creates a new instance of instruction decoder.
-    pub fn new(init_trace_length: usize) -> Decoder {
+    /// Creates a new instance of instruction decoder; the decoder panics if execution runs for
+    /// more than `max_steps` steps.
+    pub fn new(init_trace_length: usize, max_steps: usize) -> Decoder {
         // initialize operation counter
         let op_counter = vec![BaseElement::ZERO; init_trace_length];
 
@@ -70,6 +114,7 @@ impl Decoder {
         // create and return decoder
         Decoder {
             step: 0,
+            max_steps,
             op_counter,
             op_sponge,
             op_sponge_trace,
@@ -80,6 +125,10 @@ impl Decoder {
             ctx_depth,
             loop_stack,
             loop_depth,
+            #[cfg(feature = "logging")]
+            spans: vec![
+                tracing::trace_span!("program", clk = 0, hash = tracing::field::Empty).entered(),
+            ],
         }
     }
 
@@ -88,12 +137,6 @@ impl Decoder {
         self.op_counter.len()
     }
 
-    /// Returns value of the current step pointer.
-    #[allow(unused)]
-    pub fn current_step(&self) -> usize {
-        self.step
-    }
-
     /// Returns the max value of the op_counter register converted to u64.
     pub fn max_op_counter_value(&self) -> u64 {
         self.op_counter[self.op_counter.len() - 1].as_int() as u64
@@ -173,131 +216,24 @@ impl Decoder {
         registers
     }
 
-    // OPERATION DECODERS
-    // --------------------------------------------------------------------------------------------
-
-    /// Initiates a new program block (Group or Switch).
-    pub fn start_block(&mut self) {
-        assert!(
-            self.step % BASE_CYCLE_LENGTH == BASE_CYCLE_LENGTH - 1,
-            "cannot start context block at step {}: operation alignment is not valid",
-            self.step
-        );
-
-        self.advance_step(false);
-        self.save_context();
-        self.copy_loop_stack();
-        self.set_op_bits(FlowOps::Begin, UserOps::Noop);
-        self.set_sponge([BaseElement::ZERO; 4]);
-    }
-
-    /// Terminates a program block (Group, Switch, or Loop).
-    pub fn end_block(&mut self, sibling_hash: BaseElement, true_branch: bool) {
-        assert!(
-            self.step % BASE_CYCLE_LENGTH == 0,
-            "cannot exit context block at step {}: operation alignment is not valid",
-            self.step
-        );
-
-        self.advance_step(false);
-        let context_hash = self.pop_context();
-        self.copy_loop_stack();
-
-        let block_hash = self.op_sponge[0];
-        if true_branch {
-            // we are closing true branch of execution
-            self.set_op_bits(FlowOps::Tend, UserOps::Noop);
-            self.set_sponge([context_hash, block_hash, sibling_hash, BaseElement::ZERO]);
-        } else {
-            // we are closing false branch of execution
-            self.set_op_bits(FlowOps::Fend, UserOps::Noop);
-            self.set_sponge([context_hash, sibling_hash, block_hash, BaseElement::ZERO]);
-        }
-    }
-
-    /// Initiates a new Loop block
-    pub fn start_loop(&mut self, loop_image: BaseElement) {
-        assert!(
-            self.step % BASE_CYCLE_LENGTH == BASE_CYCLE_LENGTH - 1,
-            "cannot start a loop at step {}: operation alignment is not valid",
-            self.step
-        );
-
-        self.advance_step(false);
-        self.save_context();
-        self.save_loop_image(loop_image);
-        self.set_op_bits(FlowOps::Loop, UserOps::Noop);
-        self.set_sponge([BaseElement::ZERO; 4]);
-    }
-
-    /// Prepares the decoder for the next iteration of a loop.
-    pub fn wrap_loop(&mut self) {
-        assert!(
-            self.step % BASE_CYCLE_LENGTH == BASE_CYCLE_LENGTH - 1,
-            "cannot wrap a loop at step {}: operation alignment is not valid",
-            self.step
-        );
-
-        self.advance_step(false);
-        self.copy_context_stack();
-        let top_loop_image = self.peek_loop_image();
-        assert!(
-            self.op_sponge[0] == top_loop_image,
-            "cannot wrap a loop at step {}: hash of the last iteration doesn't match loop image",
-            self.step
-        );
-        self.set_op_bits(FlowOps::Wrap, UserOps::Noop);
-        self.set_sponge([BaseElement::ZERO; 4]);
-    }
-
-    /// Prepares the decoder for exiting a loop.
-    pub fn break_loop(&mut self) {
-        assert!(
-            self.step % BASE_CYCLE_LENGTH == BASE_CYCLE_LENGTH - 1,
-            "cannot break a loop at step {}: operation alignment is not valid",
-            self.step
-        );
-
-        self.advance_step(false);
-        self.copy_context_stack();
-        let top_loop_image = self.pop_loop_image();
-        assert!(
-            self.op_sponge[0] == top_loop_image,
-            "cannot break a loop at step {}: hash of the last iteration doesn't match loop image",
-            self.step
-        );
-        self.set_op_bits(FlowOps::Break, UserOps::Noop);
-        self.set_sponge(self.op_sponge);
-    }
-
-    /// Updates the decoder with the value of the specified operation.
-    pub fn decode_op(&mut self, op_code: UserOps, op_value: BaseElement) {
-        // op_value can be provided only for a PUSH operation and only
-        // at steps which are multiples of 8
-        if op_value != BaseElement::ZERO {
-            match op_code {
-                UserOps::Push => assert!(
-                    self.step % PUSH_OP_ALIGNMENT == 0,
-                    "invalid PUSH operation alignment at step {}",
-                    self.step
-                ),
-                _ => panic!(
-                    "invalid {:?} operation at step {}: op_value is non-zero",
-                    op_code, self.step
-                ),
-            }
-        }
-
-        self.advance_step(true);
-        self.copy_context_stack();
-        self.copy_loop_stack();
-        self.set_op_bits(FlowOps::Hacc, op_code);
-        self.apply_hacc_round(op_code, op_value);
-    }
-
     /// Populate all register traces with values for steps between the current step
     /// and the end of the trace.
     pub fn finalize_trace(&mut self) {
+        // if register traces were allocated for more steps than were executed, truncate them
+        // to the smallest power of 2 which fits all executed steps
+        let trace_length = cmp::max(MIN_TRACE_LENGTH, (self.step + 1).next_power_of_two());
+        if trace_length < self.trace_length() {
+            self.op_counter.truncate(trace_length);
+            self.op_sponge_trace
+                .iter_mut()
+                .chain(self.cf_op_bits.iter_mut())
+                .chain(self.ld_op_bits.iter_mut())
+                .chain(self.hd_op_bits.iter_mut())
+                .chain(self.ctx_stack.iter_mut())
+                .chain(self.loop_stack.iter_mut())
+                .for_each(|register| register.truncate(trace_length));
+        }
+
         // don't increase counter for void instructions
         let last_op_count = self.op_counter[self.step];
         fill_register(&mut self.op_counter, self.step + 1, last_op_count);
@@ -335,6 +271,12 @@ impl Decoder {
     fn advance_step(&mut self, is_user_op: bool) {
         // increment step by 1
         self.step += 1;
+        assert!(
+            self.step < self.max_steps,
+            "{} of {} steps",
+            STEP_LIMIT_EXCEEDED,
+            self.max_steps
+        );
 
         // make sure there is enough memory allocated for register traces
         if self.step >= self.trace_length() {
@@ -553,6 +495,171 @@ impl Decoder {
     }
 }
 
+// PROGRAM DECODER IMPLEMENTATION
+// ================================================================================================
+impl ProgramDecoder for Decoder {
+    /// Returns value of the current step pointer.
+    fn current_step(&self) -> usize {
+        self.step
+    }
+
+    /// Returns the value of the op_counter register at the current step.
+    fn op_counter(&self) -> BaseElement {
+        self.op_counter[self.step]
+    }
+
+    /// Returns the number of field elements allocated for all decoder register traces.
+    fn num_trace_cells(&self) -> usize {
+        let num_registers = 1
+            + op_sponge::STATE_WIDTH
+            + NUM_CF_OP_BITS
+            + NUM_LD_OP_BITS
+            + NUM_HD_OP_BITS
+            + self.ctx_stack.len()
+            + self.loop_stack.len();
+        num_registers * self.trace_length()
+    }
+
+    // OPERATION DECODERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Initiates a new program block (Group or Switch).
+    fn start_block(&mut self) {
+        assert!(
+            self.step % BASE_CYCLE_LENGTH == BASE_CYCLE_LENGTH - 1,
+            "cannot start context block at step {}: operation alignment is not valid",
+            self.step
+        );
+
+        self.advance_step(false);
+        #[cfg(feature = "logging")]
+        self.spans.push(
+            tracing::trace_span!("block", clk = self.step, hash = tracing::field::Empty).entered(),
+        );
+        self.save_context();
+        self.copy_loop_stack();
+        self.set_op_bits(FlowOps::Begin, UserOps::Noop);
+        self.set_sponge([BaseElement::ZERO; 4]);
+    }
+
+    /// Terminates a program block (Group, Switch, or Loop).
+    fn end_block(&mut self, sibling_hash: BaseElement, true_branch: bool) {
+        assert!(
+            self.step % BASE_CYCLE_LENGTH == 0,
+            "cannot exit context block at step {}: operation alignment is not valid",
+            self.step
+        );
+
+        self.advance_step(false);
+        let context_hash = self.pop_context();
+        self.copy_loop_stack();
+
+        // the hash of a block becomes known only when the block is closed; it is recorded into
+        // the span of the block which is exited here
+        let block_hash = self.op_sponge[0];
+        #[cfg(feature = "logging")]
+        if let Some(span) = self.spans.pop() {
+            span.record("hash", &tracing::field::display(block_hash));
+        }
+        if true_branch {
+            // we are closing true branch of execution
+            self.set_op_bits(FlowOps::Tend, UserOps::Noop);
+            self.set_sponge([context_hash, block_hash, sibling_hash, BaseElement::ZERO]);
+        } else {
+            // we are closing false branch of execution
+            self.set_op_bits(FlowOps::Fend, UserOps::Noop);
+            self.set_sponge([context_hash, sibling_hash, block_hash, BaseElement::ZERO]);
+        }
+    }
+
+    /// Initiates a new Loop block
+    fn start_loop(&mut self, loop_image: BaseElement) {
+        assert!(
+            self.step % BASE_CYCLE_LENGTH == BASE_CYCLE_LENGTH - 1,
+            "cannot start a loop at step {}: operation alignment is not valid",
+            self.step
+        );
+
+        self.advance_step(false);
+        #[cfg(feature = "logging")]
+        self.spans.push(
+            tracing::trace_span!("loop", clk = self.step, hash = tracing::field::Empty).entered(),
+        );
+        trace_event!(clk = self.step, image = %loop_image, "starting a loop");
+        self.save_context();
+        self.save_loop_image(loop_image);
+        self.set_op_bits(FlowOps::Loop, UserOps::Noop);
+        self.set_sponge([BaseElement::ZERO; 4]);
+    }
+
+    /// Prepares the decoder for the next iteration of a loop.
+    fn wrap_loop(&mut self) {
+        assert!(
+            self.step % BASE_CYCLE_LENGTH == BASE_CYCLE_LENGTH - 1,
+            "cannot wrap a loop at step {}: operation alignment is not valid",
+            self.step
+        );
+
+        self.advance_step(false);
+        self.copy_context_stack();
+        let top_loop_image = self.peek_loop_image();
+        assert!(
+            self.op_sponge[0] == top_loop_image,
+            "cannot wrap a loop at step {}: hash of the last iteration doesn't match loop image",
+            self.step
+        );
+        trace_event!(clk = self.step, "starting next loop iteration");
+        self.set_op_bits(FlowOps::Wrap, UserOps::Noop);
+        self.set_sponge([BaseElement::ZERO; 4]);
+    }
+
+    /// Prepares the decoder for exiting a loop.
+    fn break_loop(&mut self) {
+        assert!(
+            self.step % BASE_CYCLE_LENGTH == BASE_CYCLE_LENGTH - 1,
+            "cannot break a loop at step {}: operation alignment is not valid",
+            self.step
+        );
+
+        self.advance_step(false);
+        self.copy_context_stack();
+        let top_loop_image = self.pop_loop_image();
+        assert!(
+            self.op_sponge[0] == top_loop_image,
+            "cannot break a loop at step {}: hash of the last iteration doesn't match loop image",
+            self.step
+        );
+        trace_event!(clk = self.step, "exiting a loop");
+        self.set_op_bits(FlowOps::Break, UserOps::Noop);
+        self.set_sponge(self.op_sponge);
+    }
+
+    /// Updates the decoder with the value of the specified operation.
+    fn decode_op(&mut self, op_code: UserOps, op_value: BaseElement) {
+        // op_value can be provided only for a PUSH operation and only
+        // at steps which are multiples of 8
+        if op_value != BaseElement::ZERO {
+            match op_code {
+                UserOps::Push => assert!(
+                    self.step % PUSH_OP_ALIGNMENT == 0,
+                    "invalid PUSH operation alignment at step {}",
+                    self.step
+                ),
+                _ => panic!(
+                    "invalid {:?} operation at step {}: op_value is non-zero",
+                    op_code, self.step
+                ),
+            }
+        }
+
+        self.advance_step(true);
+        self.copy_context_stack();
+        self.copy_loop_stack();
+        self.set_op_bits(FlowOps::Hacc, op_code);
+        self.apply_hacc_round(op_code, op_value);
+    }
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 fn fill_register(register: &mut Vec<BaseElement>, from: usize, value: BaseElement) {
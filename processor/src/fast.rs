@@ -0,0 +1,278 @@
+use super::{
+    execute_program, BaseElement, FieldElement, NoHooks, OpCode, Program, ProgramDecoder,
+    ProgramInputs, SignatureError, Stack, MIN_TRACE_LENGTH,
+};
+use core::cmp;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Prefix of the panic message raised when execution exceeds the step limit of a decoder.
+pub(crate) const STEP_LIMIT_EXCEEDED: &str = "execution exceeded the step limit";
+
+// FAST EXECUTION
+// ================================================================================================
+
+/// Executes the `program` against the specified inputs and returns the top `num_outputs` items
+/// of the stack after the program completes.
+///
+/// Unlike `execute()`, this function does not build an execution trace: the program is executed
+/// by the same block walker, but operations are not absorbed into the op sponge, decoder
+/// registers are not built, and only the current state of the stack is kept. Thus, nothing can
+/// be proven about this execution, but it is considerably faster and uses memory independent of
+/// the number of executed steps. Stack operations are still executed over the same number of
+/// steps as in `execute()` (including padding NOOPs), so results of all operations (including
+/// CLK and RESCR) are identical to the ones in the trace.
+///
+/// # Errors
+/// Returns an error if the inputs do not match the number of inputs declared by the program
+/// signature, or if the stack depth after execution does not match the number of outputs
+/// declared by the program signature.
+pub fn execute_fast(
+    program: &Program,
+    inputs: &ProgramInputs,
+    num_outputs: usize,
+) -> Result<Vec<BaseElement>, SignatureError> {
+    let (_, stack) = run_program(program, inputs)?;
+    Ok(stack.get_stack_top_n(num_outputs))
+}
+
+/// Executes the `program` in the same way as `execute_fast()` and returns statistics about loops
+/// executed by the program.
+///
+/// This is useful for finding loops which execute more iterations than expected, and thus,
+/// dominate execution time of the program.
+///
+/// # Errors
+/// Returns an error if the inputs or the outputs do not match the program signature.
+pub fn profile_loops(
+    program: &Program,
+    inputs: &ProgramInputs,
+) -> Result<LoopProfile, SignatureError> {
+    let (decoder, _) = run_program(program, inputs)?;
+    Ok(decoder.loop_profile)
+}
+
+/// Executes the `program` in the same way as `execute_fast()` and returns the number of steps
+/// and the length of the execution trace which `execute()` would produce for the same inputs.
+///
+/// No trace columns are written during estimation: only the current state of the stack and the
+/// step counter are kept, so memory usage does not depend on the number of executed steps. Thus,
+/// this can be used to estimate resources needed to prove execution of the program before the
+/// trace is built.
+///
+/// # Errors
+/// Returns an error if the inputs or the outputs do not match the program signature (i.e., in
+/// the same cases in which `execute()` would fail on a signature mismatch).
+pub fn estimate_trace(
+    program: &Program,
+    inputs: &ProgramInputs,
+) -> Result<TraceEstimate, SignatureError> {
+    let (decoder, _) = run_program(program, inputs)?;
+    let num_steps = decoder.step + 1;
+    Ok(TraceEstimate {
+        num_steps,
+        trace_length: cmp::max(MIN_TRACE_LENGTH as u64, num_steps.next_power_of_two()),
+    })
+}
+
+/// Executes the `program` without building an execution trace and returns the final states of
+/// the decoder and the stack; inputs and outputs are checked against the program signature in
+/// the same way as in `execute()`.
+fn run_program(
+    program: &Program,
+    inputs: &ProgramInputs,
+) -> Result<(FastDecoder, Stack), SignatureError> {
+    let signature = program.signature();
+    signature.check_inputs(inputs)?;
+
+    let mut decoder = FastDecoder::new(u64::MAX);
+    let mut stack = Stack::without_trace(inputs);
+    execute_program(program, &mut decoder, &mut stack, &mut NoHooks);
+    signature.check_outputs(stack.depth())?;
+
+    Ok((decoder, stack))
+}
+
+/// Executes the `program` in the same way as `execute_fast()`, but without checking inputs and
+/// outputs against the program signature, and returns the number of executed steps together with
+/// the final state of the stack.
+///
+/// # Panics
+/// Panics if execution of the program fails, or if the program does not complete within
+/// `max_steps` steps; in the latter case, the panic message starts with `STEP_LIMIT_EXCEEDED`.
+#[cfg(feature = "std")]
+pub(crate) fn execute_with_step_limit(
+    program: &Program,
+    inputs: &ProgramInputs,
+    max_steps: u64,
+) -> (u64, Stack) {
+    let mut decoder = FastDecoder::new(max_steps);
+    let mut stack = Stack::without_trace(inputs);
+    execute_program(program, &mut decoder, &mut stack, &mut NoHooks);
+    (decoder.step + 1, stack)
+}
+
+// TRACE ESTIMATE
+// ================================================================================================
+
+/// Size of an execution trace as predicted by `estimate_trace()`.
+///
+/// Sizes are reported as u64 values because the number of steps executed by a program is not
+/// limited by the memory available to the processor when no trace is built (e.g., it may exceed
+/// `usize::MAX` on 32-bit platforms).
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TraceEstimate {
+    /// Number of steps executed by the program (not including padding steps).
+    pub num_steps: u64,
+    /// Length of the execution trace; this is always a power of two.
+    pub trace_length: u64,
+}
+
+// LOOP PROFILE
+// ================================================================================================
+
+/// Statistics about loops executed by a program.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LoopProfile {
+    /// Maximum loop nesting depth reached during execution.
+    pub max_depth: usize,
+    /// Statistics for each loop which was entered at least once, in the order in which loops
+    /// were first entered. Loops with identical bodies are reported as a single loop.
+    pub loops: Vec<LoopStats>,
+}
+
+/// Execution statistics for a single loop.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LoopStats {
+    /// Image of the loop body (the same value which is pushed onto the loop stack).
+    pub image: BaseElement,
+    /// Nesting depth of the loop when it was first entered; the outermost loop has depth 1.
+    pub depth: usize,
+    /// Number of times the loop was entered.
+    pub entries: u64,
+    /// Total number of iterations executed across all entries.
+    pub iterations: u64,
+    /// Maximum number of iterations executed during a single entry.
+    pub max_iterations: u64,
+}
+
+// FAST DECODER
+// ================================================================================================
+
+/// Decoder which keeps track only of the current step, the op counter, and statistics about
+/// executed loops; no decoder registers are recorded.
+///
+/// Since no register traces are allocated, the number of steps is not bounded by available
+/// memory; thus, steps are counted using u64 values with checked arithmetic.
+struct FastDecoder {
+    step: u64,
+    max_steps: u64,
+    op_counter: BaseElement,
+    loop_stack: Vec<(usize, u64)>,
+    loop_profile: LoopProfile,
+}
+
+impl FastDecoder {
+    fn new(max_steps: u64) -> Self {
+        FastDecoder {
+            step: 0,
+            max_steps,
+            op_counter: BaseElement::ZERO,
+            loop_stack: Vec::new(),
+            loop_profile: LoopProfile::default(),
+        }
+    }
+
+    /// Advances the decoder by one step.
+    ///
+    /// # Panics
+    /// Panics if the number of executed steps exceeds the step limit of this decoder.
+    fn advance_step(&mut self) {
+        self.step = self.step.checked_add(1).expect("step counter overflow");
+        assert!(
+            self.step < self.max_steps,
+            "{} of {} steps",
+            STEP_LIMIT_EXCEEDED,
+            self.max_steps
+        );
+    }
+
+    /// Returns an index of statistics for the loop with the specified image; if the loop is
+    /// entered for the first time, empty statistics for it are added to the loop profile.
+    fn get_loop_stats_index(&mut self, image: BaseElement, depth: usize) -> usize {
+        let loops = &mut self.loop_profile.loops;
+        match loops.iter().position(|stats| stats.image == image) {
+            Some(idx) => idx,
+            None => {
+                loops.push(LoopStats {
+                    image,
+                    depth,
+                    entries: 0,
+                    iterations: 0,
+                    max_iterations: 0,
+                });
+                loops.len() - 1
+            }
+        }
+    }
+}
+
+impl ProgramDecoder for FastDecoder {
+    /// Returns the current step truncated to usize; the step is used only to build execution
+    /// states for hooks, which are never invoked during fast execution.
+    fn current_step(&self) -> usize {
+        self.step as usize
+    }
+
+    fn op_counter(&self) -> BaseElement {
+        self.op_counter
+    }
+
+    fn num_trace_cells(&self) -> usize {
+        0
+    }
+
+    fn start_block(&mut self) {
+        self.advance_step();
+    }
+
+    fn end_block(&mut self, _sibling_hash: BaseElement, _true_branch: bool) {
+        self.advance_step();
+    }
+
+    fn start_loop(&mut self, loop_image: BaseElement) {
+        self.advance_step();
+
+        // the loop stack holds the index of loop statistics and the number of iterations
+        // executed since the loop was entered for every loop which is currently executing
+        let depth = self.loop_stack.len() + 1;
+        let stats_idx = self.get_loop_stats_index(loop_image, depth);
+        self.loop_stack.push((stats_idx, 0));
+        self.loop_profile.max_depth = cmp::max(self.loop_profile.max_depth, depth);
+    }
+
+    fn wrap_loop(&mut self) {
+        self.advance_step();
+        self.loop_stack.last_mut().expect("loop stack is empty").1 += 1;
+    }
+
+    fn break_loop(&mut self) {
+        self.advance_step();
+        let (stats_idx, num_iterations) = self.loop_stack.pop().expect("loop stack is empty");
+        let num_iterations = num_iterations + 1;
+
+        let stats = &mut self.loop_profile.loops[stats_idx];
+        stats.entries += 1;
+        stats.iterations = stats
+            .iterations
+            .checked_add(num_iterations)
+            .expect("loop iteration counter overflow");
+        stats.max_iterations = cmp::max(stats.max_iterations, num_iterations);
+    }
+
+    fn decode_op(&mut self, _op_code: OpCode, _op_value: BaseElement) {
+        self.advance_step();
+        self.op_counter += BaseElement::ONE;
+    }
+}
@@ -0,0 +1,107 @@
+use super::{BaseElement, OpCode, ProgramDecoder, Stack};
+use vm_core::program::blocks::ProgramBlock;
+
+// EXECUTION HOOKS
+// ================================================================================================
+
+/// Callbacks invoked by the processor around execution of program blocks and user operations.
+///
+/// All methods have empty default implementations, so an implementor needs to override only
+/// the callbacks it is interested in. Callbacks receive a read-only view of the processor state
+/// and cannot affect execution.
+pub trait ExecutionHooks {
+    /// Invoked before the processor starts executing the specified block.
+    fn before_block(&mut self, _block: &ProgramBlock, _state: &ExecutionState) {}
+
+    /// Invoked after the processor finished executing the specified block.
+    fn after_block(&mut self, _block: &ProgramBlock, _state: &ExecutionState) {}
+
+    /// Invoked before the processor executes the specified user operation.
+    fn before_op(&mut self, _op_code: OpCode, _state: &ExecutionState) {}
+
+    /// Invoked after the processor executed the specified user operation.
+    fn after_op(&mut self, _op_code: OpCode, _state: &ExecutionState) {}
+}
+
+/// Hooks which do nothing; these are used when executing programs without hooks.
+pub struct NoHooks;
+
+impl ExecutionHooks for NoHooks {}
+
+// EXECUTION STATE
+// ================================================================================================
+
+/// Read-only view of the processor state passed to [ExecutionHooks].
+pub struct ExecutionState<'a> {
+    step: usize,
+    op_counter: BaseElement,
+    decoder_trace_cells: usize,
+    stack: &'a Stack,
+}
+
+impl<'a> ExecutionState<'a> {
+    pub(super) fn new<D: ProgramDecoder>(decoder: &D, stack: &'a Stack) -> Self {
+        ExecutionState {
+            step: decoder.current_step(),
+            op_counter: decoder.op_counter(),
+            decoder_trace_cells: decoder.num_trace_cells(),
+            stack,
+        }
+    }
+
+    /// Returns the current step of the execution trace.
+    pub fn step(&self) -> usize {
+        self.step
+    }
+
+    /// Returns the number of user operations executed so far.
+    pub fn op_counter(&self) -> BaseElement {
+        self.op_counter
+    }
+
+    /// Returns the current depth of the stack.
+    pub fn stack_depth(&self) -> usize {
+        self.stack.depth()
+    }
+
+    /// Returns the top `n` items of the stack.
+    pub fn stack_top(&self, n: usize) -> Vec<BaseElement> {
+        self.stack.get_stack_top_n(n)
+    }
+
+    /// Returns statistics about memory used by the processor so far.
+    #[cfg(feature = "hooks")]
+    pub fn memory_stats(&self) -> MemoryStats {
+        MemoryStats {
+            max_stack_depth: self.stack.max_depth(),
+            tape_lengths: self.stack.tape_lengths(),
+            decoder_trace_cells: self.decoder_trace_cells,
+            stack_trace_cells: self.stack.num_trace_cells(),
+        }
+    }
+}
+
+// MEMORY STATS
+// ================================================================================================
+
+/// Memory used by the processor at some point during execution of a program.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct MemoryStats {
+    /// Maximum depth of the stack reached so far.
+    pub max_stack_depth: usize,
+    /// Number of values remaining on secret input tapes A and B; this does not include values
+    /// generated from a tape seed once a tape runs out.
+    pub tape_lengths: [usize; 2],
+    /// Number of field elements allocated for decoder register traces.
+    pub decoder_trace_cells: usize,
+    /// Number of field elements allocated for stack register traces; register traces grow as
+    /// the number of executed steps or the depth of the stack increases.
+    pub stack_trace_cells: usize,
+}
+
+impl MemoryStats {
+    /// Returns the total number of field elements allocated for register traces.
+    pub fn trace_cells(&self) -> usize {
+        self.decoder_trace_cells + self.stack_trace_cells
+    }
+}
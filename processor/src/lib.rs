@@ -7,17 +7,59 @@ use vm_core::{
     PUSH_OP_ALIGNMENT,
 };
 
+// LOGGING
+// ================================================================================================
+
+/// Emits a trace-level event when `logging` feature is enabled; otherwise, does nothing.
+macro_rules! trace_event {
+    ($($arg:tt)+) => {
+        #[cfg(feature = "logging")]
+        tracing::trace!($($arg)+);
+    };
+}
+
 mod decoder;
-use decoder::Decoder;
+use decoder::{Decoder, ProgramDecoder};
 
 mod stack;
 use stack::Stack;
 
+mod fast;
+pub use fast::{
+    estimate_trace, execute_fast, profile_loops, LoopProfile, LoopStats, TraceEstimate,
+};
+
+mod options;
+pub use options::ExecutionOptions;
+
+#[cfg_attr(not(feature = "hooks"), allow(dead_code))]
+mod hooks;
+use hooks::NoHooks;
+#[cfg(not(feature = "hooks"))]
+use hooks::{ExecutionHooks, ExecutionState};
+#[cfg(feature = "hooks")]
+pub use hooks::{ExecutionHooks, ExecutionState, MemoryStats};
+#[cfg(feature = "hooks")]
+pub use vm_core::program::blocks;
+
+#[cfg(all(feature = "hooks", feature = "std"))]
+mod timing;
+#[cfg(all(feature = "hooks", feature = "std"))]
+pub use timing::{OpTiming, OpTimings};
+
+#[cfg(feature = "std")]
+pub mod mutation;
+
+#[cfg(feature = "std")]
+mod batch;
+#[cfg(feature = "std")]
+pub use batch::{execute_batch, ExecutionFailure};
+
 // EXPORTS
 // ================================================================================================
 
 pub use vm_core::{
-    program::{Program, ProgramInputs},
+    program::{InputError, Program, ProgramInputs, ProgramSignature, SignatureError},
     BaseElement, FieldElement, StarkField,
 };
 pub use winterfell::ExecutionTrace;
@@ -26,16 +68,73 @@ pub use winterfell::ExecutionTrace;
 // ================================================================================================
 
 /// Returns register traces resulting from executing the `program` against the specified inputs.
+///
+/// # Panics
+/// Panics if execution of the program fails, including the case when the inputs or the outputs
+/// do not match the program signature; use `try_execute()` to handle signature mismatches as
+/// errors.
 pub fn execute(program: &Program, inputs: &ProgramInputs) -> ExecutionTrace<BaseElement> {
+    try_execute(program, inputs).unwrap_or_else(|err| panic!("{}", err))
+}
+
+/// Returns register traces resulting from executing the `program` against the specified inputs.
+///
+/// # Errors
+/// Returns an error if the inputs do not match the number of inputs declared by the program
+/// signature, or if the stack depth after execution does not match the number of outputs
+/// declared by the program signature; in the former case, the program is not executed.
+pub fn try_execute(
+    program: &Program,
+    inputs: &ProgramInputs,
+) -> Result<ExecutionTrace<BaseElement>, SignatureError> {
+    build_trace(program, inputs, &ExecutionOptions::default(), &mut NoHooks)
+}
+
+/// Returns register traces resulting from executing the `program` against the specified inputs;
+/// the trace is built according to the specified `options`.
+pub fn execute_with_options(
+    program: &Program,
+    inputs: &ProgramInputs,
+    options: &ExecutionOptions,
+) -> ExecutionTrace<BaseElement> {
+    build_trace(program, inputs, options, &mut NoHooks).unwrap_or_else(|err| panic!("{}", err))
+}
+
+/// Returns register traces resulting from executing the `program` against the specified inputs;
+/// the provided `hooks` are invoked around execution of every program block and user operation.
+#[cfg(feature = "hooks")]
+pub fn execute_with_hooks<H: ExecutionHooks>(
+    program: &Program,
+    inputs: &ProgramInputs,
+    hooks: &mut H,
+) -> ExecutionTrace<BaseElement> {
+    build_trace(program, inputs, &ExecutionOptions::default(), hooks)
+        .unwrap_or_else(|err| panic!("{}", err))
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+fn build_trace<H: ExecutionHooks>(
+    program: &Program,
+    inputs: &ProgramInputs,
+    options: &ExecutionOptions,
+    hooks: &mut H,
+) -> Result<ExecutionTrace<BaseElement>, SignatureError> {
+    // make sure inputs match the ones declared by the program
+    let signature = program.signature();
+    signature.check_inputs(inputs)?;
+
     // initialize decoder and stack components
-    let mut decoder = Decoder::new(MIN_TRACE_LENGTH);
-    let mut stack = Stack::new(inputs, MIN_TRACE_LENGTH);
+    let init_trace_length = options.init_trace_length();
+    let mut decoder = Decoder::new(init_trace_length, options.max_cycles());
+    let mut stack = Stack::new(inputs, init_trace_length);
 
     // execute body of the program
-    execute_blocks(program.root().body(), &mut decoder, &mut stack);
-    close_block(&mut decoder, &mut stack, BaseElement::ZERO, true);
+    execute_program(program, &mut decoder, &mut stack, hooks);
+    signature.check_outputs(stack.depth())?;
 
     // fill in remaining steps to make sure the length of the trace is a power of 2
+    let num_steps = decoder.current_step() as u64 + 1;
     decoder.finalize_trace();
     stack.finalize_trace();
 
@@ -46,6 +145,7 @@ pub fn execute(program: &Program, inputs: &ProgramInputs) -> ExecutionTrace<Base
     let mut meta = op_counter.to_le_bytes().to_vec();
     meta.push(context_depth as u8);
     meta.push(loop_depth as u8);
+    meta.extend_from_slice(&num_steps.to_le_bytes());
 
     // merge decoder and stack register traces into a single vector
     let mut register_traces = decoder.into_register_traces();
@@ -54,66 +154,127 @@ pub fn execute(program: &Program, inputs: &ProgramInputs) -> ExecutionTrace<Base
     let mut trace = ExecutionTrace::init(register_traces);
     trace.set_meta(meta);
 
-    trace
+    Ok(trace)
 }
 
-// HELPER FUNCTIONS
-// ================================================================================================
-fn execute_blocks(blocks: &[ProgramBlock], decoder: &mut Decoder, stack: &mut Stack) {
-    // execute first block in the sequence, which mast be a Span block
-    match &blocks[0] {
-        ProgramBlock::Span(block) => execute_span(block, decoder, stack, true),
-        _ => panic!("first block in a sequence must be a Span block"),
-    }
+/// Executes all blocks of the `program`; the `decoder` determines which decoder registers (if any)
+/// are recorded during execution.
+fn execute_program<D: ProgramDecoder, H: ExecutionHooks>(
+    program: &Program,
+    decoder: &mut D,
+    stack: &mut Stack,
+    hooks: &mut H,
+) {
+    execute_blocks(program.root().body(), decoder, stack, hooks);
+    close_block(decoder, stack, BaseElement::ZERO, true);
+}
 
-    // execute all other blocks in the sequence one after another
-    for block in blocks.iter().skip(1) {
-        match block {
-            ProgramBlock::Span(block) => execute_span(block, decoder, stack, false),
-            ProgramBlock::Group(block) => {
-                start_block(decoder, stack);
-                execute_blocks(block.body(), decoder, stack);
-                close_block(decoder, stack, BaseElement::ZERO, true);
+/// Executes a sequence of program blocks.
+///
+/// Nested blocks are executed iteratively: the work remaining in every block which is currently
+/// being executed is kept on an explicit stack of tasks rather than on the call stack, and thus,
+/// execution of deeply nested programs cannot overflow the call stack.
+fn execute_blocks<D: ProgramDecoder, H: ExecutionHooks>(
+    blocks: &[ProgramBlock],
+    decoder: &mut D,
+    stack: &mut Stack,
+    hooks: &mut H,
+) {
+    let mut tasks = vec![BlockTask::Sequence(blocks, 0)];
+    while let Some(task) = tasks.pop() {
+        match task {
+            BlockTask::Sequence(blocks, i) => {
+                if i == blocks.len() {
+                    continue;
+                }
+                // the rest of the sequence is executed after the current block is finished
+                tasks.push(BlockTask::Sequence(blocks, i + 1));
+                start_sequence_block(&blocks[i], i == 0, &mut tasks, decoder, stack, hooks);
+            }
+            BlockTask::Close(block, sibling_hash, is_true_branch) => {
+                close_block(decoder, stack, sibling_hash, is_true_branch);
+                hooks.after_block(block, &ExecutionState::new(decoder, stack));
             }
-            ProgramBlock::Switch(block) => {
-                start_block(decoder, stack);
-                let condition = stack.get_stack_top();
-                match condition {
-                    BaseElement::ZERO => {
-                        execute_blocks(block.false_branch(), decoder, stack);
-                        close_block(decoder, stack, block.true_branch_hash(), false);
-                    }
-                    BaseElement::ONE => {
-                        execute_blocks(block.true_branch(), decoder, stack);
-                        close_block(decoder, stack, block.false_branch_hash(), true);
-                    }
-                    _ => panic!(
-                        "cannot select a branch based on a non-binary condition {}",
-                        condition
-                    ),
-                };
+            BlockTask::LoopIteration(block, loop_block) => {
+                end_loop_iteration(block, loop_block, &mut tasks, decoder, stack, hooks);
             }
-            ProgramBlock::Loop(block) => {
-                let condition = stack.get_stack_top();
-                match condition {
-                    BaseElement::ZERO => {
-                        start_block(decoder, stack);
-                        execute_blocks(block.skip(), decoder, stack);
-                        close_block(decoder, stack, block.body_hash(), false);
-                    }
-                    BaseElement::ONE => execute_loop(block, decoder, stack),
-                    _ => panic!(
-                        "cannot enter loop based on a non-binary condition {}",
-                        condition
-                    ),
+        }
+    }
+}
+
+/// Starts executing the specified block of a sequence; Span blocks are executed right away, while
+/// for all other blocks, the work needed to execute them is pushed onto the `tasks` stack.
+fn start_sequence_block<'a, D: ProgramDecoder, H: ExecutionHooks>(
+    block: &'a ProgramBlock,
+    is_first: bool,
+    tasks: &mut Vec<BlockTask<'a>>,
+    decoder: &mut D,
+    stack: &mut Stack,
+    hooks: &mut H,
+) {
+    hooks.before_block(block, &ExecutionState::new(decoder, stack));
+    match block {
+        ProgramBlock::Span(span) => {
+            execute_span(span, decoder, stack, is_first, hooks);
+            hooks.after_block(block, &ExecutionState::new(decoder, stack));
+        }
+        // first block in the sequence must be a Span block
+        _ if is_first => panic!("first block in a sequence must be a Span block"),
+        ProgramBlock::Group(group) => {
+            start_block(decoder, stack);
+            tasks.push(BlockTask::Close(block, BaseElement::ZERO, true));
+            tasks.push(BlockTask::Sequence(group.body(), 0));
+        }
+        ProgramBlock::Switch(switch) => {
+            start_block(decoder, stack);
+            let condition = stack.get_stack_top();
+            match condition {
+                BaseElement::ZERO => {
+                    tasks.push(BlockTask::Close(block, switch.true_branch_hash(), false));
+                    tasks.push(BlockTask::Sequence(switch.false_branch(), 0));
+                }
+                BaseElement::ONE => {
+                    tasks.push(BlockTask::Close(block, switch.false_branch_hash(), true));
+                    tasks.push(BlockTask::Sequence(switch.true_branch(), 0));
+                }
+                _ => panic!(
+                    "cannot select a branch based on a non-binary condition {}",
+                    condition
+                ),
+            };
+        }
+        ProgramBlock::Loop(loop_block) => {
+            let condition = stack.get_stack_top();
+            match condition {
+                BaseElement::ZERO => {
+                    start_block(decoder, stack);
+                    tasks.push(BlockTask::Close(block, loop_block.body_hash(), false));
+                    tasks.push(BlockTask::Sequence(loop_block.skip(), 0));
+                }
+                BaseElement::ONE => {
+                    // mark the beginning of the loop block
+                    decoder.start_loop(loop_block.image());
+                    stack.execute(OpCode::Noop, OpHint::None);
+                    tasks.push(BlockTask::LoopIteration(block, loop_block));
+                    tasks.push(BlockTask::Sequence(loop_block.body(), 0));
                 }
+                _ => panic!(
+                    "cannot enter loop based on a non-binary condition {}",
+                    condition
+                ),
             }
         }
     }
 }
 
 /// Executes all instructions in a Span block.
-fn execute_span(block: &Span, decoder: &mut Decoder, stack: &mut Stack, is_first: bool) {
+fn execute_span<D: ProgramDecoder, H: ExecutionHooks>(
+    block: &Span,
+    decoder: &mut D,
+    stack: &mut Stack,
+    is_first: bool,
+    hooks: &mut H,
+) {
     // if this is the first Span block in a sequence of blocks, it needs to be
     // pre-padded with a NOOP to make sure the first instruction in the block
     // starts executing on a step which is a multiple of 16
@@ -125,20 +286,27 @@ fn execute_span(block: &Span, decoder: &mut Decoder, stack: &mut Stack, is_first
     // execute all other instructions in the block
     for i in 0..block.length() {
         let (op_code, op_hint) = block.get_op(i);
+        hooks.before_op(op_code, &ExecutionState::new(decoder, stack));
         decoder.decode_op(op_code, op_hint.value());
-        stack.execute(op_code, op_hint);
+        match op_code {
+            // CLK operation pushes the value of the op counter (including CLK itself) onto the
+            // stack; this value is tracked by the decoder
+            OpCode::Clk => stack.execute(op_code, OpHint::PushValue(decoder.op_counter())),
+            _ => stack.execute(op_code, op_hint),
+        }
+        hooks.after_op(op_code, &ExecutionState::new(decoder, stack));
     }
 }
 
 /// Starts executing a new program block.
-fn start_block(decoder: &mut Decoder, stack: &mut Stack) {
+fn start_block<D: ProgramDecoder>(decoder: &mut D, stack: &mut Stack) {
     decoder.start_block();
     stack.execute(OpCode::Noop, OpHint::None);
 }
 
 /// Closes the currently executing program block.
-fn close_block(
-    decoder: &mut Decoder,
+fn close_block<D: ProgramDecoder>(
+    decoder: &mut D,
     stack: &mut Stack,
     sibling_hash: BaseElement,
     is_true_branch: bool,
@@ -161,40 +329,55 @@ fn close_block(
     }
 }
 
-/// Executes the specified loop.
-fn execute_loop(block: &Loop, decoder: &mut Decoder, stack: &mut Stack) {
-    // mark the beginning of the loop block
-    decoder.start_loop(block.image());
-    stack.execute(OpCode::Noop, OpHint::None);
-
-    // execute blocks in loop body until top of the stack becomes 0
-    loop {
-        execute_blocks(block.body(), decoder, stack);
-
-        let condition = stack.get_stack_top();
-        match condition {
-            BaseElement::ZERO => {
-                decoder.break_loop();
-                stack.execute(OpCode::Noop, OpHint::None);
-                break;
-            }
-            BaseElement::ONE => {
-                decoder.wrap_loop();
-                stack.execute(OpCode::Noop, OpHint::None);
-            }
-            _ => panic!(
-                "cannot exit loop based on a non-binary condition {}",
-                condition
-            ),
-        };
-    }
+/// Finishes an iteration of the specified loop: if top of the stack is 1, schedules the next
+/// iteration of the loop; otherwise, exits the loop.
+fn end_loop_iteration<'a, D: ProgramDecoder, H: ExecutionHooks>(
+    block: &'a ProgramBlock,
+    loop_block: &'a Loop,
+    tasks: &mut Vec<BlockTask<'a>>,
+    decoder: &mut D,
+    stack: &mut Stack,
+    hooks: &mut H,
+) {
+    let condition = stack.get_stack_top();
+    match condition {
+        BaseElement::ZERO => {
+            decoder.break_loop();
+            stack.execute(OpCode::Noop, OpHint::None);
+        }
+        BaseElement::ONE => {
+            decoder.wrap_loop();
+            stack.execute(OpCode::Noop, OpHint::None);
+            tasks.push(BlockTask::LoopIteration(block, loop_block));
+            tasks.push(BlockTask::Sequence(loop_block.body(), 0));
+            return;
+        }
+        _ => panic!(
+            "cannot exit loop based on a non-binary condition {}",
+            condition
+        ),
+    };
 
     // execute the contents of the skip block to make sure the loop was exited correctly
-    match &block.skip()[0] {
-        ProgramBlock::Span(block) => execute_span(block, decoder, stack, true),
+    match &loop_block.skip()[0] {
+        ProgramBlock::Span(span) => execute_span(span, decoder, stack, true, hooks),
         _ => panic!("invalid skip block content: content must be a Span block"),
     }
 
     // close block
-    close_block(decoder, stack, block.skip_hash(), true);
+    close_block(decoder, stack, loop_block.skip_hash(), true);
+    hooks.after_block(block, &ExecutionState::new(decoder, stack));
+}
+
+// BLOCK TASKS
+// ================================================================================================
+
+/// Work remaining in a program block which is currently being executed.
+enum BlockTask<'a> {
+    /// Execute blocks of a sequence starting with the block at the specified index.
+    Sequence(&'a [ProgramBlock], usize),
+    /// Close the specified block using the specified sibling hash and branch flag.
+    Close(&'a ProgramBlock, BaseElement, bool),
+    /// Check the condition at the end of an iteration of the specified loop.
+    LoopIteration(&'a ProgramBlock, &'a Loop),
 }
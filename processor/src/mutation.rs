@@ -0,0 +1,193 @@
+use super::fast::{execute_with_step_limit, STEP_LIMIT_EXCEEDED};
+use std::{
+    any::Any,
+    cmp,
+    collections::BTreeMap,
+    panic::{catch_unwind, AssertUnwindSafe},
+};
+use vm_core::{
+    opcodes::OpHint,
+    program::{
+        blocks::{Group, Loop, ProgramBlock, Span, Switch},
+        Program, ProgramInputs,
+    },
+    BaseElement, FieldElement, MIN_TRACE_LENGTH,
+};
+
+// EXPORTS
+// ================================================================================================
+
+pub use vm_core::opcodes::UserOps as OpCode;
+
+// MUTATION RESULTS
+// ================================================================================================
+
+/// Outcome of executing a program with a single operation perturbed.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum MutationOutcome {
+    /// The program executed successfully and produced the same outputs as the original program;
+    /// this usually indicates dead code or insufficient test inputs.
+    Survived,
+    /// The program executed successfully but produced outputs different from the outputs of the
+    /// original program.
+    OutputChanged,
+    /// Execution of the program failed (e.g. an assertion failed or the stack underflowed).
+    ExecutionFailed,
+    /// The program did not complete within twice the number of steps executed by the original
+    /// program (but no fewer than `MIN_TRACE_LENGTH` steps); this usually indicates that the
+    /// perturbation prevented a loop from terminating.
+    Timeout,
+}
+
+/// Describes a single perturbation of a program and the outcome of executing it.
+#[derive(Copy, Clone, Debug)]
+pub struct MutationResult {
+    /// Index of the perturbed operation among all operations eligible for perturbation.
+    pub index: usize,
+    /// The operation which was perturbed.
+    pub op_code: OpCode,
+    /// The outcome of executing the perturbed program.
+    pub outcome: MutationOutcome,
+}
+
+// PUBLIC FUNCTIONS
+// ================================================================================================
+
+/// Executes the `program` once for every operation in it with that operation perturbed, and
+/// reports whether the top `num_outputs` stack items at the end of execution have changed.
+///
+/// Operations are perturbed as follows:
+/// * PUSH operations have their immediate value incremented by 1.
+/// * All other operations, except for BEGIN and NOOP, are replaced with a NOOP.
+///
+/// All programs are executed without building an execution trace, and inputs and outputs are
+/// not checked against the program signature. Perturbed programs are stopped once they execute
+/// twice as many steps as the original program, and are reported as `Timeout`.
+///
+/// Results are returned in the order in which operations appear in the program. Execution
+/// failures of perturbed programs are caught and reported as `ExecutionFailed`; however, the
+/// panic messages will still be printed by the default panic hook.
+///
+/// # Panics
+/// Panics if the unperturbed program fails to execute against the specified inputs.
+pub fn execute_mutations(
+    program: &Program,
+    inputs: &ProgramInputs,
+    num_outputs: usize,
+) -> Vec<MutationResult> {
+    let (num_steps, stack) = execute_with_step_limit(program, inputs, u64::MAX);
+    let expected = stack.get_stack_top_n(num_outputs);
+    let max_steps = cmp::max(num_steps.saturating_mul(2), MIN_TRACE_LENGTH as u64);
+
+    let mut result = Vec::new();
+    let mut index = 0;
+    while let Some((mutant, op_code)) = mutate_program(program, index) {
+        let outcome = match catch_unwind(AssertUnwindSafe(|| {
+            let (_, stack) = execute_with_step_limit(&mutant, inputs, max_steps);
+            stack.get_stack_top_n(num_outputs)
+        })) {
+            Ok(outputs) if outputs == expected => MutationOutcome::Survived,
+            Ok(_) => MutationOutcome::OutputChanged,
+            Err(payload) if is_step_limit_panic(payload.as_ref()) => MutationOutcome::Timeout,
+            Err(_) => MutationOutcome::ExecutionFailed,
+        };
+
+        result.push(MutationResult {
+            index,
+            op_code,
+            outcome,
+        });
+        index += 1;
+    }
+
+    result
+}
+
+/// Returns a copy of the `program` with the operation at the specified index perturbed, together
+/// with the original operation; the index is counted among operations eligible for perturbation
+/// (i.e. all operations except for BEGIN and NOOP). Returns None if the program contains fewer
+/// than `index + 1` eligible operations.
+pub fn mutate_program(program: &Program, index: usize) -> Option<(Program, OpCode)> {
+    let mut mutator = Mutator::new(index);
+    let body = mutator.mutate_blocks(program.root().body());
+    mutator
+        .mutated_op
+        .map(|op_code| (Program::new(Group::new(body)), op_code))
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns true if the panic `payload` was raised because execution exceeded the step limit.
+fn is_step_limit_panic(payload: &(dyn Any + Send)) -> bool {
+    payload
+        .downcast_ref::<String>()
+        .map_or(false, |message| message.starts_with(STEP_LIMIT_EXCEEDED))
+}
+
+// PROGRAM MUTATOR
+// ================================================================================================
+
+/// Rebuilds a program block by block, perturbing a single operation along the way.
+struct Mutator {
+    target: usize,
+    next_index: usize,
+    mutated_op: Option<OpCode>,
+}
+
+impl Mutator {
+    fn new(target: usize) -> Self {
+        Mutator {
+            target,
+            next_index: 0,
+            mutated_op: None,
+        }
+    }
+
+    fn mutate_blocks(&mut self, blocks: &[ProgramBlock]) -> Vec<ProgramBlock> {
+        blocks
+            .iter()
+            .map(|block| match block {
+                ProgramBlock::Span(block) => ProgramBlock::Span(self.mutate_span(block)),
+                ProgramBlock::Group(block) => Group::new_block(self.mutate_blocks(block.body())),
+                ProgramBlock::Switch(block) => {
+                    let t_branch = self.mutate_blocks(block.true_branch());
+                    let f_branch = self.mutate_blocks(block.false_branch());
+                    Switch::new_block(t_branch, f_branch)
+                }
+                ProgramBlock::Loop(block) => Loop::new_block(self.mutate_blocks(block.body())),
+            })
+            .collect()
+    }
+
+    fn mutate_span(&mut self, span: &Span) -> Span {
+        let mut op_codes = Vec::with_capacity(span.length());
+        let mut op_hints = BTreeMap::new();
+
+        for i in 0..span.length() {
+            let (mut op_code, mut op_hint) = span.get_op(i);
+            if op_code != OpCode::Begin && op_code != OpCode::Noop {
+                if self.next_index == self.target {
+                    self.mutated_op = Some(op_code);
+                    if op_code == OpCode::Push {
+                        op_hint = OpHint::PushValue(op_hint.value() + BaseElement::ONE);
+                    } else {
+                        // hints carrying values are valid only for PUSH operations
+                        op_code = OpCode::Noop;
+                        if let OpHint::PushValue(_) = op_hint {
+                            op_hint = OpHint::None;
+                        }
+                    }
+                }
+                self.next_index += 1;
+            }
+
+            op_codes.push(op_code);
+            if !matches!(op_hint, OpHint::None) {
+                op_hints.insert(i, op_hint);
+            }
+        }
+
+        Span::new(op_codes, op_hints)
+    }
+}
@@ -0,0 +1,68 @@
+use super::MIN_TRACE_LENGTH;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Maximum length with which register traces are initialized; hints of a larger number of cycles
+/// are capped at this length, and register traces grow beyond it as usual.
+const MAX_INIT_TRACE_LENGTH: usize = 1 << 20;
+
+// EXECUTION OPTIONS
+// ================================================================================================
+
+/// Options which affect how the processor builds an execution trace; these options do not affect
+/// the contents of the trace, but execution is aborted if it exceeds the specified limits.
+#[derive(Clone, Debug)]
+pub struct ExecutionOptions {
+    expected_cycles: usize,
+    max_cycles: usize,
+}
+
+impl ExecutionOptions {
+    /// Returns options with the expected number of cycles set to the minimum trace length, and
+    /// with no limit on the number of cycles.
+    pub fn new() -> Self {
+        ExecutionOptions {
+            expected_cycles: MIN_TRACE_LENGTH,
+            max_cycles: usize::MAX,
+        }
+    }
+
+    /// Returns these options modified with a hint of how many cycles the program is expected to
+    /// run for. Memory for register traces is allocated upfront based on this hint, so that
+    /// register traces do not need to be reallocated as the trace grows. The hint does not need
+    /// to be exact: if it is too small, register traces grow as usual. Hints above 2^20 cycles are
+    /// treated as 2^20 cycles, so that an oversized hint cannot exhaust memory upfront.
+    pub fn with_expected_cycles(mut self, num_cycles: usize) -> Self {
+        self.expected_cycles = num_cycles;
+        self
+    }
+
+    /// Returns these options modified with the maximum number of cycles the program may run for
+    /// (not counting cycles added to pad the trace to a power of two). Execution of a program
+    /// which exceeds this limit is aborted with a panic, so that a program which does not
+    /// terminate (or runs for too long) cannot exhaust memory by growing its execution trace.
+    pub fn with_max_cycles(mut self, num_cycles: usize) -> Self {
+        self.max_cycles = num_cycles;
+        self
+    }
+
+    /// Returns the maximum number of cycles the program may run for.
+    pub(super) fn max_cycles(&self) -> usize {
+        self.max_cycles
+    }
+
+    /// Returns the length with which register traces should be initialized; this is always a
+    /// power of two between the minimum trace length and 2^20.
+    pub(super) fn init_trace_length(&self) -> usize {
+        self.expected_cycles
+            .clamp(MIN_TRACE_LENGTH, MAX_INIT_TRACE_LENGTH)
+            .next_power_of_two()
+    }
+}
+
+impl Default for ExecutionOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
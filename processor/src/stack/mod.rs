@@ -1,8 +1,9 @@
 use crate::{
     hasher, BaseElement, FieldElement, OpCode, OpHint, ProgramInputs, StarkField, MAX_STACK_DEPTH,
-    MIN_STACK_DEPTH,
+    MIN_STACK_DEPTH, MIN_TRACE_LENGTH,
 };
 use core::cmp;
+use vm_core::utils::TapeRng;
 
 #[cfg(test)]
 mod tests;
@@ -13,9 +14,14 @@ pub struct Stack {
     registers: Vec<Vec<BaseElement>>,
     tape_a: Vec<BaseElement>,
     tape_b: Vec<BaseElement>,
+    tape_rng: Option<TapeRng>,
     max_depth: usize,
     depth: usize,
     step: usize,
+    cur: usize,
+    prev: usize,
+    keep_trace: bool,
+    op_code: OpCode,
 }
 
 // STACK IMPLEMENTATION
@@ -48,21 +54,36 @@ impl Stack {
             registers,
             tape_a,
             tape_b,
+            tape_rng: inputs.tape_seed().map(TapeRng::new),
             max_depth: public_inputs.len(),
             depth: public_inputs.len(),
             step: 0,
+            cur: 0,
+            prev: 0,
+            keep_trace: true,
+            op_code: OpCode::Noop,
         }
     }
 
+    /// Returns a new Stack which keeps only the current and the previous states of the stack
+    /// rather than full register traces; such a stack can be used to execute programs, but cannot
+    /// be converted into register traces.
+    pub fn without_trace(inputs: &ProgramInputs) -> Stack {
+        let mut stack = Self::new(inputs, 2);
+        stack.keep_trace = false;
+        stack
+    }
+
     /// Executes `opcode` against the current state of the stack.
     pub fn execute(&mut self, op_code: OpCode, op_hint: OpHint) {
         // increment step pointer and make sure there is enough memory allocated to hold the trace
         self.advance_step();
+        self.op_code = op_code;
 
         // execute the appropriate action against the current state of the stack
         match op_code {
             OpCode::Begin => self.op_noop(),
-            OpCode::Noop => self.op_noop(),
+            OpCode::Noop => self.op_noop_with_hint(op_hint),
 
             OpCode::Assert => self.op_assert(),
             OpCode::AssertEq => self.op_asserteq(),
@@ -70,6 +91,8 @@ impl Stack {
             OpCode::Push => self.op_push(op_hint),
             OpCode::Read => self.op_read(op_hint),
             OpCode::Read2 => self.op_read2(op_hint),
+            OpCode::Clk => self.op_clk(op_hint),
+            OpCode::SDepth => self.op_sdepth(),
 
             OpCode::Dup => self.op_dup(),
             OpCode::Dup2 => self.op_dup2(),
@@ -89,6 +112,8 @@ impl Stack {
             OpCode::Choose => self.op_choose(),
             OpCode::Choose2 => self.op_choose2(),
             OpCode::CSwap2 => self.op_cswap2(),
+            OpCode::CSwap => self.op_cswap(),
+            OpCode::CSwapW => self.op_cswapw(),
 
             OpCode::Add => self.op_add(),
             OpCode::Mul => self.op_mul(),
@@ -117,22 +142,66 @@ impl Stack {
         self.step
     }
 
+    /// Returns the current depth of the stack.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns the maximum depth of the stack reached so far.
+    #[cfg(feature = "hooks")]
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    /// Returns the number of values remaining on secret input tapes A and B.
+    #[cfg(feature = "hooks")]
+    pub fn tape_lengths(&self) -> [usize; 2] {
+        [self.tape_a.len(), self.tape_b.len()]
+    }
+
+    /// Returns the number of field elements allocated for stack register traces.
+    #[cfg(feature = "hooks")]
+    pub fn num_trace_cells(&self) -> usize {
+        self.registers.len() * self.trace_length()
+    }
+
     /// Returns the value at the top of the stack at the current step.
     pub fn get_stack_top(&self) -> BaseElement {
-        self.registers[0][self.step]
+        self.registers[0][self.cur]
+    }
+
+    /// Returns the top `n` values of the stack at the current step.
+    pub fn get_stack_top_n(&self, n: usize) -> Vec<BaseElement> {
+        self.registers
+            .iter()
+            .take(n)
+            .map(|register| register[self.cur])
+            .collect()
     }
 
     /// Populate all register traces with values for steps between the current step
     /// and the end of the trace.
     pub fn finalize_trace(&mut self) {
-        let trace_length = self.trace_length();
+        assert!(
+            self.keep_trace,
+            "cannot finalize trace of a stack without trace"
+        );
+
+        // if register traces were allocated for more steps than were executed, use the smallest
+        // power of 2 which fits all executed steps as the trace length
+        let trace_length = cmp::min(
+            self.trace_length(),
+            cmp::max(MIN_TRACE_LENGTH, (self.step + 1).next_power_of_two()),
+        );
         for register in self.registers.iter_mut() {
             register.resize(self.step + 1, BaseElement::ZERO);
-            register.resize(trace_length, register[self.step]);
+            register.resize(trace_length, register[self.cur]);
         }
 
         // update the step pointer to point to the last step
         self.step = self.trace_length() - 1;
+        self.cur = self.step;
+        self.prev = self.step - 1;
     }
 
     /// Merges all register traces into a single vector of traces.
@@ -147,9 +216,28 @@ impl Stack {
         self.copy_state(0);
     }
 
+    fn op_noop_with_hint(&mut self, hint: OpHint) {
+        self.copy_state(0);
+
+        // stack assertions are checked only in debug builds
+        if let OpHint::AssertStackTop(n, expected) = hint {
+            if cfg!(debug_assertions) {
+                let expected = &expected[..(n as usize)];
+                let actual = self.get_stack_top_n(expected.len());
+                assert!(
+                    actual == expected,
+                    "stack assertion failed at step {}: expected top of the stack to be {:?}, but was {:?}",
+                    self.step,
+                    expected,
+                    actual
+                );
+            }
+        }
+    }
+
     fn op_assert(&mut self) {
-        assert!(self.depth >= 1, "stack underflow at step {}", self.step);
-        let value = self.registers[0][self.step - 1];
+        self.assert_depth(1);
+        let value = self.registers[0][self.prev];
         assert!(
             value == BaseElement::ONE,
             "ASSERT failed at step {}",
@@ -159,9 +247,9 @@ impl Stack {
     }
 
     fn op_asserteq(&mut self) {
-        assert!(self.depth >= 2, "stack underflow at step {}", self.step);
-        let x = self.registers[0][self.step - 1];
-        let y = self.registers[1][self.step - 1];
+        self.assert_depth(2);
+        let x = self.registers[0][self.prev];
+        let y = self.registers[1][self.prev];
         assert!(x == y, "ASSERTEQ failed at step {}", self.step);
         self.shift_left(2, 2);
     }
@@ -174,7 +262,22 @@ impl Stack {
             OpHint::PushValue(value) => value,
             _ => panic!("invalid value for PUSH operation at step {}", self.step),
         };
-        self.registers[0][self.step] = op_value;
+        self.registers[0][self.cur] = op_value;
+    }
+
+    fn op_clk(&mut self, hint: OpHint) {
+        self.shift_right(0, 1);
+        let op_count = match hint {
+            OpHint::PushValue(value) => value,
+            _ => panic!("invalid value for CLK operation at step {}", self.step),
+        };
+        self.registers[0][self.cur] = op_count;
+    }
+
+    fn op_sdepth(&mut self) {
+        let depth = BaseElement::new(self.depth as u128);
+        self.shift_right(0, 1);
+        self.registers[0][self.cur] = depth;
     }
 
     fn op_read(&mut self, hint: OpHint) {
@@ -184,9 +287,9 @@ impl Stack {
                 // if we are about to equality comparison sequence, push inverse of the difference
                 // between top two stack values onto secret tape A, if they are equal; otherwise
                 // push value 1
-                assert!(self.depth >= 2, "stack underflow at step {}", self.step);
-                let x = self.registers[0][self.step - 1];
-                let y = self.registers[1][self.step - 1];
+                self.assert_depth(2);
+                let x = self.registers[0][self.prev];
+                let y = self.registers[1][self.prev];
                 if x == y {
                     self.tape_a.push(BaseElement::ONE);
                 } else {
@@ -194,6 +297,7 @@ impl Stack {
                 }
             }
             OpHint::None => {
+                refill_tape(&mut self.tape_a, &mut self.tape_rng);
                 assert!(
                     !self.tape_a.is_empty(),
                     "attempt to read from empty tape A at step {}",
@@ -205,14 +309,15 @@ impl Stack {
 
         self.shift_right(0, 1);
         let value = self.tape_a.pop().unwrap();
-        self.registers[0][self.step] = value;
+        trace_event!(clk = self.step, value = %value, "read from tape A");
+        self.registers[0][self.cur] = value;
     }
 
     fn op_read2(&mut self, hint: OpHint) {
         // process execution hint
         match hint {
             OpHint::PmpathStart(n) => {
-                assert!(self.depth >= 3, "stack underflow at step {}", self.step);
+                self.assert_depth(3);
 
                 let n = (n - 1) as usize;
                 assert!(
@@ -224,7 +329,7 @@ impl Stack {
                     "too few items on tape B for pmpath macro"
                 );
 
-                let idx = self.registers[2][self.step - 1];
+                let idx = self.registers[2][self.prev];
 
                 // we need to insert binary decomposition of index into tape A, but we need to make
                 // sure it is interlaced with node values already present there. To do this,
@@ -240,6 +345,8 @@ impl Stack {
                 }
             }
             OpHint::None => {
+                refill_tape(&mut self.tape_a, &mut self.tape_rng);
+                refill_tape(&mut self.tape_b, &mut self.tape_rng);
                 assert!(
                     !self.tape_a.is_empty(),
                     "attempt to read from empty tape A at step {}",
@@ -257,110 +364,116 @@ impl Stack {
         self.shift_right(0, 2);
         let value_a = self.tape_a.pop().unwrap();
         let value_b = self.tape_b.pop().unwrap();
-        self.registers[0][self.step] = value_b;
-        self.registers[1][self.step] = value_a;
+        trace_event!(
+            clk = self.step,
+            value_a = %value_a,
+            value_b = %value_b,
+            "read from tapes A and B"
+        );
+        self.registers[0][self.cur] = value_b;
+        self.registers[1][self.cur] = value_a;
     }
 
     // STACK MANIPULATION OPERATIONS
     // --------------------------------------------------------------------------------------------
     fn op_dup(&mut self) {
-        assert!(self.depth >= 1, "stack underflow at step {}", self.step);
+        self.assert_depth(1);
         self.shift_right(0, 1);
-        self.registers[0][self.step] = self.registers[0][self.step - 1];
+        self.registers[0][self.cur] = self.registers[0][self.prev];
     }
 
     fn op_dup2(&mut self) {
-        assert!(self.depth >= 2, "stack underflow at step {}", self.step);
+        self.assert_depth(2);
         self.shift_right(0, 2);
-        self.registers[0][self.step] = self.registers[0][self.step - 1];
-        self.registers[1][self.step] = self.registers[1][self.step - 1];
+        self.registers[0][self.cur] = self.registers[0][self.prev];
+        self.registers[1][self.cur] = self.registers[1][self.prev];
     }
 
     fn op_dup4(&mut self) {
-        assert!(self.depth >= 4, "stack underflow at step {}", self.step);
+        self.assert_depth(4);
         self.shift_right(0, 4);
-        self.registers[0][self.step] = self.registers[0][self.step - 1];
-        self.registers[1][self.step] = self.registers[1][self.step - 1];
-        self.registers[2][self.step] = self.registers[2][self.step - 1];
-        self.registers[3][self.step] = self.registers[3][self.step - 1];
+        self.registers[0][self.cur] = self.registers[0][self.prev];
+        self.registers[1][self.cur] = self.registers[1][self.prev];
+        self.registers[2][self.cur] = self.registers[2][self.prev];
+        self.registers[3][self.cur] = self.registers[3][self.prev];
     }
 
     fn op_pad2(&mut self) {
         self.shift_right(0, 2);
-        self.registers[0][self.step] = BaseElement::ZERO;
-        self.registers[1][self.step] = BaseElement::ZERO;
+        self.registers[0][self.cur] = BaseElement::ZERO;
+        self.registers[1][self.cur] = BaseElement::ZERO;
     }
 
     fn op_drop(&mut self) {
-        assert!(self.depth >= 1, "stack underflow at step {}", self.step);
+        self.assert_depth(1);
         self.shift_left(1, 1);
     }
 
     fn op_drop4(&mut self) {
-        assert!(self.depth >= 4, "stack underflow at step {}", self.step);
+        self.assert_depth(4);
         self.shift_left(4, 4);
     }
 
     fn op_swap(&mut self) {
-        assert!(self.depth >= 2, "stack underflow at step {}", self.step);
-        self.registers[0][self.step] = self.registers[1][self.step - 1];
-        self.registers[1][self.step] = self.registers[0][self.step - 1];
+        self.assert_depth(2);
+        self.registers[0][self.cur] = self.registers[1][self.prev];
+        self.registers[1][self.cur] = self.registers[0][self.prev];
         self.copy_state(2);
     }
 
     fn op_swap2(&mut self) {
-        assert!(self.depth >= 4, "stack underflow at step {}", self.step);
-        self.registers[0][self.step] = self.registers[2][self.step - 1];
-        self.registers[1][self.step] = self.registers[3][self.step - 1];
-        self.registers[2][self.step] = self.registers[0][self.step - 1];
-        self.registers[3][self.step] = self.registers[1][self.step - 1];
+        self.assert_depth(4);
+        self.registers[0][self.cur] = self.registers[2][self.prev];
+        self.registers[1][self.cur] = self.registers[3][self.prev];
+        self.registers[2][self.cur] = self.registers[0][self.prev];
+        self.registers[3][self.cur] = self.registers[1][self.prev];
         self.copy_state(4);
     }
 
     fn op_swap4(&mut self) {
-        assert!(self.depth >= 8, "stack underflow at step {}", self.step);
-        self.registers[0][self.step] = self.registers[4][self.step - 1];
-        self.registers[1][self.step] = self.registers[5][self.step - 1];
-        self.registers[2][self.step] = self.registers[6][self.step - 1];
-        self.registers[3][self.step] = self.registers[7][self.step - 1];
-        self.registers[4][self.step] = self.registers[0][self.step - 1];
-        self.registers[5][self.step] = self.registers[1][self.step - 1];
-        self.registers[6][self.step] = self.registers[2][self.step - 1];
-        self.registers[7][self.step] = self.registers[3][self.step - 1];
+        self.assert_depth(8);
+        self.registers[0][self.cur] = self.registers[4][self.prev];
+        self.registers[1][self.cur] = self.registers[5][self.prev];
+        self.registers[2][self.cur] = self.registers[6][self.prev];
+        self.registers[3][self.cur] = self.registers[7][self.prev];
+        self.registers[4][self.cur] = self.registers[0][self.prev];
+        self.registers[5][self.cur] = self.registers[1][self.prev];
+        self.registers[6][self.cur] = self.registers[2][self.prev];
+        self.registers[7][self.cur] = self.registers[3][self.prev];
         self.copy_state(8);
     }
 
     fn op_roll4(&mut self) {
-        assert!(self.depth >= 4, "stack underflow at step {}", self.step);
-        self.registers[0][self.step] = self.registers[3][self.step - 1];
-        self.registers[1][self.step] = self.registers[0][self.step - 1];
-        self.registers[2][self.step] = self.registers[1][self.step - 1];
-        self.registers[3][self.step] = self.registers[2][self.step - 1];
+        self.assert_depth(4);
+        self.registers[0][self.cur] = self.registers[3][self.prev];
+        self.registers[1][self.cur] = self.registers[0][self.prev];
+        self.registers[2][self.cur] = self.registers[1][self.prev];
+        self.registers[3][self.cur] = self.registers[2][self.prev];
         self.copy_state(4);
     }
 
     fn op_roll8(&mut self) {
-        assert!(self.depth >= 8, "stack underflow at step {}", self.step);
-        self.registers[0][self.step] = self.registers[7][self.step - 1];
-        self.registers[1][self.step] = self.registers[0][self.step - 1];
-        self.registers[2][self.step] = self.registers[1][self.step - 1];
-        self.registers[3][self.step] = self.registers[2][self.step - 1];
-        self.registers[4][self.step] = self.registers[3][self.step - 1];
-        self.registers[5][self.step] = self.registers[4][self.step - 1];
-        self.registers[6][self.step] = self.registers[5][self.step - 1];
-        self.registers[7][self.step] = self.registers[6][self.step - 1];
+        self.assert_depth(8);
+        self.registers[0][self.cur] = self.registers[7][self.prev];
+        self.registers[1][self.cur] = self.registers[0][self.prev];
+        self.registers[2][self.cur] = self.registers[1][self.prev];
+        self.registers[3][self.cur] = self.registers[2][self.prev];
+        self.registers[4][self.cur] = self.registers[3][self.prev];
+        self.registers[5][self.cur] = self.registers[4][self.prev];
+        self.registers[6][self.cur] = self.registers[5][self.prev];
+        self.registers[7][self.cur] = self.registers[6][self.prev];
         self.copy_state(8);
     }
 
     // SELECTION OPERATIONS
     // --------------------------------------------------------------------------------------------
     fn op_choose(&mut self) {
-        assert!(self.depth >= 3, "stack underflow at step {}", self.step);
-        let condition = self.registers[2][self.step - 1];
+        self.assert_depth(3);
+        let condition = self.registers[2][self.prev];
         if condition == BaseElement::ONE {
-            self.registers[0][self.step] = self.registers[0][self.step - 1];
+            self.registers[0][self.cur] = self.registers[0][self.prev];
         } else if condition == BaseElement::ZERO {
-            self.registers[0][self.step] = self.registers[1][self.step - 1];
+            self.registers[0][self.cur] = self.registers[1][self.prev];
         } else {
             panic!("CHOOSE on a non-binary condition at step {}", self.step);
         }
@@ -368,14 +481,14 @@ impl Stack {
     }
 
     fn op_choose2(&mut self) {
-        assert!(self.depth >= 6, "stack underflow at step {}", self.step);
-        let condition = self.registers[4][self.step - 1];
+        self.assert_depth(6);
+        let condition = self.registers[4][self.prev];
         if condition == BaseElement::ONE {
-            self.registers[0][self.step] = self.registers[0][self.step - 1];
-            self.registers[1][self.step] = self.registers[1][self.step - 1];
+            self.registers[0][self.cur] = self.registers[0][self.prev];
+            self.registers[1][self.cur] = self.registers[1][self.prev];
         } else if condition == BaseElement::ZERO {
-            self.registers[0][self.step] = self.registers[2][self.step - 1];
-            self.registers[1][self.step] = self.registers[3][self.step - 1];
+            self.registers[0][self.cur] = self.registers[2][self.prev];
+            self.registers[1][self.cur] = self.registers[3][self.prev];
         } else {
             panic!("CHOOSE2 on a non-binary condition at step {}", self.step);
         }
@@ -383,78 +496,112 @@ impl Stack {
     }
 
     fn op_cswap2(&mut self) {
-        assert!(self.depth >= 6, "stack underflow at step {}", self.step);
-        let condition = self.registers[4][self.step - 1];
+        self.assert_depth(6);
+        let condition = self.registers[4][self.prev];
         if condition == BaseElement::ZERO {
-            self.registers[0][self.step] = self.registers[0][self.step - 1];
-            self.registers[1][self.step] = self.registers[1][self.step - 1];
-            self.registers[2][self.step] = self.registers[2][self.step - 1];
-            self.registers[3][self.step] = self.registers[3][self.step - 1];
+            self.registers[0][self.cur] = self.registers[0][self.prev];
+            self.registers[1][self.cur] = self.registers[1][self.prev];
+            self.registers[2][self.cur] = self.registers[2][self.prev];
+            self.registers[3][self.cur] = self.registers[3][self.prev];
         } else if condition == BaseElement::ONE {
-            self.registers[0][self.step] = self.registers[2][self.step - 1];
-            self.registers[1][self.step] = self.registers[3][self.step - 1];
-            self.registers[2][self.step] = self.registers[0][self.step - 1];
-            self.registers[3][self.step] = self.registers[1][self.step - 1];
+            self.registers[0][self.cur] = self.registers[2][self.prev];
+            self.registers[1][self.cur] = self.registers[3][self.prev];
+            self.registers[2][self.cur] = self.registers[0][self.prev];
+            self.registers[3][self.cur] = self.registers[1][self.prev];
         } else {
             panic!("CSWAP2 on a non-binary condition at step {}", self.step);
         }
         self.shift_left(6, 2);
     }
 
+    fn op_cswap(&mut self) {
+        self.assert_depth(3);
+        let condition = self.registers[0][self.prev];
+        if condition == BaseElement::ZERO {
+            self.registers[0][self.cur] = self.registers[1][self.prev];
+            self.registers[1][self.cur] = self.registers[2][self.prev];
+        } else if condition == BaseElement::ONE {
+            self.registers[0][self.cur] = self.registers[2][self.prev];
+            self.registers[1][self.cur] = self.registers[1][self.prev];
+        } else {
+            panic!("CSWAP on a non-binary condition at step {}", self.step);
+        }
+        self.shift_left(3, 1);
+    }
+
+    fn op_cswapw(&mut self) {
+        self.assert_depth(5);
+        let condition = self.registers[0][self.prev];
+        if condition == BaseElement::ZERO {
+            self.registers[0][self.cur] = self.registers[1][self.prev];
+            self.registers[1][self.cur] = self.registers[2][self.prev];
+            self.registers[2][self.cur] = self.registers[3][self.prev];
+            self.registers[3][self.cur] = self.registers[4][self.prev];
+        } else if condition == BaseElement::ONE {
+            self.registers[0][self.cur] = self.registers[3][self.prev];
+            self.registers[1][self.cur] = self.registers[4][self.prev];
+            self.registers[2][self.cur] = self.registers[1][self.prev];
+            self.registers[3][self.cur] = self.registers[2][self.prev];
+        } else {
+            panic!("CSWAPW on a non-binary condition at step {}", self.step);
+        }
+        self.shift_left(5, 1);
+    }
+
     // ARITHMETIC AND BOOLEAN OPERATIONS
     // --------------------------------------------------------------------------------------------
     fn op_add(&mut self) {
-        assert!(self.depth >= 2, "stack underflow at step {}", self.step);
-        let x = self.registers[0][self.step - 1];
-        let y = self.registers[1][self.step - 1];
-        self.registers[0][self.step] = x + y;
+        self.assert_depth(2);
+        let x = self.registers[0][self.prev];
+        let y = self.registers[1][self.prev];
+        self.registers[0][self.cur] = x + y;
         self.shift_left(2, 1);
     }
 
     fn op_mul(&mut self) {
-        assert!(self.depth >= 2, "stack underflow at step {}", self.step);
-        let x = self.registers[0][self.step - 1];
-        let y = self.registers[1][self.step - 1];
-        self.registers[0][self.step] = x * y;
+        self.assert_depth(2);
+        let x = self.registers[0][self.prev];
+        let y = self.registers[1][self.prev];
+        self.registers[0][self.cur] = x * y;
         self.shift_left(2, 1);
     }
 
     fn op_inv(&mut self) {
-        assert!(self.depth >= 1, "stack underflow at step {}", self.step);
-        let x = self.registers[0][self.step - 1];
+        self.assert_depth(1);
+        let x = self.registers[0][self.prev];
         assert!(
             x != BaseElement::ZERO,
             "cannot compute INV of {} at step {}",
             BaseElement::ZERO,
             self.step
         );
-        self.registers[0][self.step] = x.inv();
+        self.registers[0][self.cur] = x.inv();
         self.copy_state(1);
     }
 
     fn op_neg(&mut self) {
-        assert!(self.depth >= 1, "stack underflow at step {}", self.step);
-        let x = self.registers[0][self.step - 1];
-        self.registers[0][self.step] = -x;
+        self.assert_depth(1);
+        let x = self.registers[0][self.prev];
+        self.registers[0][self.cur] = -x;
         self.copy_state(1);
     }
 
     fn op_not(&mut self) {
-        assert!(self.depth >= 1, "stack underflow at step {}", self.step);
-        let x = self.registers[0][self.step - 1];
+        self.assert_depth(1);
+        let x = self.registers[0][self.prev];
         assert!(
             is_binary(x),
             "cannot compute NOT of a non-binary value at step {}",
             self.step
         );
-        self.registers[0][self.step] = BaseElement::ONE - x;
+        self.registers[0][self.cur] = BaseElement::ONE - x;
         self.copy_state(1);
     }
 
     fn op_and(&mut self) {
-        assert!(self.depth >= 2, "stack underflow at step {}", self.step);
-        let x = self.registers[0][self.step - 1];
-        let y = self.registers[1][self.step - 1];
+        self.assert_depth(2);
+        let x = self.registers[0][self.prev];
+        let y = self.registers[1][self.prev];
         assert!(
             is_binary(x),
             "cannot compute AND for a non-binary value at step {}",
@@ -466,7 +613,7 @@ impl Stack {
             self.step
         );
 
-        self.registers[0][self.step] = if x == BaseElement::ONE && y == BaseElement::ONE {
+        self.registers[0][self.cur] = if x == BaseElement::ONE && y == BaseElement::ONE {
             BaseElement::ONE
         } else {
             BaseElement::ZERO
@@ -475,9 +622,9 @@ impl Stack {
     }
 
     fn op_or(&mut self) {
-        assert!(self.depth >= 2, "stack underflow at step {}", self.step);
-        let x = self.registers[0][self.step - 1];
-        let y = self.registers[1][self.step - 1];
+        self.assert_depth(2);
+        let x = self.registers[0][self.prev];
+        let y = self.registers[1][self.prev];
         assert!(
             is_binary(x),
             "cannot compute OR for a non-binary value at step {}",
@@ -489,7 +636,7 @@ impl Stack {
             self.step
         );
 
-        self.registers[0][self.step] = if x == BaseElement::ONE || y == BaseElement::ONE {
+        self.registers[0][self.cur] = if x == BaseElement::ONE || y == BaseElement::ONE {
             BaseElement::ONE
         } else {
             BaseElement::ZERO
@@ -500,12 +647,12 @@ impl Stack {
     // COMPARISON OPERATIONS
     // --------------------------------------------------------------------------------------------
     fn op_eq(&mut self) {
-        assert!(self.depth >= 3, "stack underflow at step {}", self.step);
-        let aux = self.registers[0][self.step - 1];
-        let x = self.registers[1][self.step - 1];
-        let y = self.registers[2][self.step - 1];
+        self.assert_depth(3);
+        let aux = self.registers[0][self.prev];
+        let x = self.registers[1][self.prev];
+        let y = self.registers[2][self.prev];
         if x == y {
-            self.registers[0][self.step] = BaseElement::ONE;
+            self.registers[0][self.cur] = BaseElement::ONE;
         } else {
             let diff = x - y;
             assert!(
@@ -513,7 +660,7 @@ impl Stack {
                 "invalid AUX value for EQ operation at step {}",
                 self.step
             );
-            self.registers[0][self.step] = BaseElement::ZERO;
+            self.registers[0][self.cur] = BaseElement::ZERO;
         }
         self.shift_left(3, 2);
     }
@@ -524,9 +671,9 @@ impl Stack {
             OpHint::CmpStart(n) => {
                 // if we are about to start comparison sequence, push binary decompositions
                 // of a and b values onto the tapes
-                assert!(self.depth >= 10, "stack underflow at step {}", self.step);
-                let a_val = self.registers[8][self.step - 1];
-                let b_val = self.registers[9][self.step - 1];
+                self.assert_depth(10);
+                let a_val = self.registers[8][self.prev];
+                let b_val = self.registers[9][self.prev];
                 for i in 0..n {
                     self.tape_a
                         .push(BaseElement::new((a_val.as_int() >> i) & 1));
@@ -535,7 +682,7 @@ impl Stack {
                 }
             }
             OpHint::None => {
-                assert!(self.depth >= 8, "stack underflow at step {}", self.step);
+                self.assert_depth(8);
                 assert!(
                     !self.tape_a.is_empty(),
                     "attempt to read from empty tape A at step {}",
@@ -571,7 +718,7 @@ impl Stack {
         let bit_lt = b_bit * (BaseElement::ONE - a_bit);
 
         // compute current power of 2 for binary decomposition
-        let power_of_two = self.registers[0][self.step - 1];
+        let power_of_two = self.registers[0][self.prev];
         assert!(
             power_of_two.as_int().is_power_of_two(),
             "expected top of the stack at step {} to be a power of 2, but received {}",
@@ -585,19 +732,19 @@ impl Stack {
         };
 
         // determine if the result of comparison is already known
-        let gt = self.registers[4][self.step - 1];
-        let lt = self.registers[5][self.step - 1];
+        let gt = self.registers[4][self.prev];
+        let lt = self.registers[5][self.prev];
         let not_set = (BaseElement::ONE - gt) * (BaseElement::ONE - lt);
 
         // update the next state of the computation
-        self.registers[0][self.step] = next_power_of_two;
-        self.registers[1][self.step] = a_bit;
-        self.registers[2][self.step] = b_bit;
-        self.registers[3][self.step] = not_set;
-        self.registers[4][self.step] = gt + bit_gt * not_set;
-        self.registers[5][self.step] = lt + bit_lt * not_set;
-        self.registers[6][self.step] = self.registers[6][self.step - 1] + b_bit * power_of_two;
-        self.registers[7][self.step] = self.registers[7][self.step - 1] + a_bit * power_of_two;
+        self.registers[0][self.cur] = next_power_of_two;
+        self.registers[1][self.cur] = a_bit;
+        self.registers[2][self.cur] = b_bit;
+        self.registers[3][self.cur] = not_set;
+        self.registers[4][self.cur] = gt + bit_gt * not_set;
+        self.registers[5][self.cur] = lt + bit_lt * not_set;
+        self.registers[6][self.cur] = self.registers[6][self.prev] + b_bit * power_of_two;
+        self.registers[7][self.cur] = self.registers[7][self.prev] + a_bit * power_of_two;
 
         self.copy_state(8);
     }
@@ -608,8 +755,8 @@ impl Stack {
             OpHint::RcStart(n) => {
                 // if we are about to start range check sequence, push binary decompositions
                 // of the value onto tape A
-                assert!(self.depth >= 5, "stack underflow at step {}", self.step);
-                let val = self.registers[4][self.step - 1];
+                self.assert_depth(5);
+                let val = self.registers[4][self.prev];
                 for i in 0..n {
                     // most significant bit is pushed first
                     self.tape_a
@@ -617,7 +764,7 @@ impl Stack {
                 }
             }
             OpHint::None => {
-                assert!(self.depth >= 4, "stack underflow at step {}", self.step);
+                self.assert_depth(4);
                 assert!(
                     !self.tape_a.is_empty(),
                     "attempt to read from empty tape A at step {}",
@@ -640,19 +787,19 @@ impl Stack {
         );
 
         // compute current power of 2 for binary decomposition
-        let power_of_two = self.registers[2][self.step - 1];
+        let power_of_two = self.registers[2][self.prev];
         assert!(power_of_two.as_int().is_power_of_two(),
             "expected 3rd value from the top of the stack at step {} to be a power of 2, but received {}",
             self.step, power_of_two);
         let next_power_of_two = power_of_two * BaseElement::new(2);
 
-        let acc = self.registers[3][self.step - 1];
+        let acc = self.registers[3][self.prev];
 
         // update the next state of the computation
-        self.registers[0][self.step] = bit;
-        self.registers[1][self.step] = BaseElement::ZERO;
-        self.registers[2][self.step] = next_power_of_two;
-        self.registers[3][self.step] = acc + bit * power_of_two;
+        self.registers[0][self.cur] = bit;
+        self.registers[1][self.cur] = BaseElement::ZERO;
+        self.registers[2][self.cur] = next_power_of_two;
+        self.registers[3][self.cur] = acc + bit * power_of_two;
 
         self.copy_state(4);
     }
@@ -660,28 +807,25 @@ impl Stack {
     // CRYPTOGRAPHIC OPERATIONS
     // --------------------------------------------------------------------------------------------
     fn op_rescr(&mut self) {
-        assert!(
-            self.depth >= hasher::STATE_WIDTH,
-            "stack underflow at step {}",
-            self.step
-        );
+        self.assert_depth(hasher::STATE_WIDTH);
         let mut state = [
-            self.registers[0][self.step - 1],
-            self.registers[1][self.step - 1],
-            self.registers[2][self.step - 1],
-            self.registers[3][self.step - 1],
-            self.registers[4][self.step - 1],
-            self.registers[5][self.step - 1],
+            self.registers[0][self.prev],
+            self.registers[1][self.prev],
+            self.registers[2][self.prev],
+            self.registers[3][self.prev],
+            self.registers[4][self.prev],
+            self.registers[5][self.prev],
         ];
 
-        hasher::apply_round(&mut state, self.step - 1);
+        trace_event!(clk = self.step, "applying a round of the hash function");
+        hasher::apply_round(&mut state, self.step.wrapping_sub(1));
 
-        self.registers[0][self.step] = state[0];
-        self.registers[1][self.step] = state[1];
-        self.registers[2][self.step] = state[2];
-        self.registers[3][self.step] = state[3];
-        self.registers[4][self.step] = state[4];
-        self.registers[5][self.step] = state[5];
+        self.registers[0][self.cur] = state[0];
+        self.registers[1][self.cur] = state[1];
+        self.registers[2][self.cur] = state[2];
+        self.registers[3][self.cur] = state[3];
+        self.registers[4][self.cur] = state[4];
+        self.registers[5][self.cur] = state[5];
 
         self.copy_state(hasher::STATE_WIDTH);
     }
@@ -689,27 +833,35 @@ impl Stack {
     // HELPER METHODS
     // --------------------------------------------------------------------------------------------
 
+    /// Makes sure the stack holds at least `num_items` items required by the current operation.
+    fn assert_depth(&self, num_items: usize) {
+        assert!(
+            self.depth >= num_items,
+            "stack underflow at step {}: {} requires {} stack items, but stack depth is {}",
+            self.step,
+            self.op_code.to_string().to_uppercase(),
+            num_items,
+            self.depth
+        );
+    }
+
     fn copy_state(&mut self, start: usize) {
         for i in start..self.depth {
-            self.registers[i][self.step] = self.registers[i][self.step - 1];
+            self.registers[i][self.cur] = self.registers[i][self.prev];
         }
     }
 
     fn shift_left(&mut self, start: usize, pos_count: usize) {
-        assert!(
-            self.depth >= pos_count,
-            "stack underflow at step {}",
-            self.step
-        );
+        self.assert_depth(pos_count);
 
         // shift all values by pos_count to the left
         for i in start..self.depth {
-            self.registers[i - pos_count][self.step] = self.registers[i][self.step - 1];
+            self.registers[i - pos_count][self.cur] = self.registers[i][self.prev];
         }
 
         // set all "shifted-in" slots to 0
         for i in (self.depth - pos_count)..self.depth {
-            self.registers[i][self.step] = BaseElement::ZERO;
+            self.registers[i][self.cur] = BaseElement::ZERO;
         }
 
         // stack depth has been reduced by pos_count
@@ -732,7 +884,7 @@ impl Stack {
         }
 
         for i in start..(self.depth - pos_count) {
-            self.registers[i + pos_count][self.step] = self.registers[i][self.step - 1];
+            self.registers[i + pos_count][self.cur] = self.registers[i][self.prev];
         }
     }
 
@@ -745,14 +897,33 @@ impl Stack {
     }
 
     fn advance_step(&mut self) {
-        // increment step by 1
-        self.step += 1;
-
-        // make sure there is enough memory allocated for register traces
-        if self.step >= self.trace_length() {
-            let new_length = self.trace_length() * 2;
-            for register in self.registers.iter_mut() {
-                register.resize(new_length, BaseElement::ZERO);
+        // increment step by 1; when no trace is kept, the step is used only to look up hash
+        // round constants, which repeat every BASE_CYCLE_LENGTH steps, so the step is allowed to
+        // wrap around (e.g., on 32-bit platforms)
+        self.step = if self.keep_trace {
+            self.step + 1
+        } else {
+            self.step.wrapping_add(1)
+        };
+
+        if self.keep_trace {
+            // make sure there is enough memory allocated for register traces
+            if self.step >= self.trace_length() {
+                let new_length = self.trace_length() * 2;
+                for register in self.registers.iter_mut() {
+                    register.resize(new_length, BaseElement::ZERO);
+                }
+            }
+            self.cur = self.step;
+            self.prev = self.step - 1;
+        } else {
+            // registers hold only two states, so the state of the step before the previous one
+            // is overwritten; operations write all slots below stack depth, and thus, only slots
+            // beyond the depth need to be reset to keep them equal to the ones in a full trace
+            self.prev = self.cur;
+            self.cur = self.step % 2;
+            for register in self.registers.iter_mut().skip(self.depth) {
+                register[self.cur] = BaseElement::ZERO;
             }
         }
     }
@@ -763,3 +934,11 @@ impl Stack {
 fn is_binary(value: BaseElement) -> bool {
     value == BaseElement::ZERO || value == BaseElement::ONE
 }
+
+/// Pushes a pseudo-random value onto the tape if the tape is empty and a tape seed was provided.
+fn refill_tape(tape: &mut Vec<BaseElement>, rng: &mut Option<TapeRng>) {
+    match rng {
+        Some(rng) if tape.is_empty() => tape.push(rng.next_element()),
+        _ => (),
+    }
+}
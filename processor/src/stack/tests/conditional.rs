@@ -90,3 +90,55 @@ fn cswap2_fail() {
     let mut stack = init_stack(&[2, 3, 4, 5, 6, 8, 8], &[], &[], TRACE_LENGTH);
     stack.execute(OpCode::CSwap2, OpHint::None);
 }
+
+#[test]
+fn cswap() {
+    // don't swap on false
+    let mut stack = init_stack(&[0, 2, 3, 4], &[], &[], TRACE_LENGTH);
+    stack.execute(OpCode::CSwap, OpHint::None);
+    assert_eq!(vec![2, 3, 4, 0, 0, 0, 0, 0], get_stack_state(&stack, 1));
+
+    assert_eq!(3, stack.depth);
+    assert_eq!(4, stack.max_depth);
+
+    // swap on true
+    let mut stack = init_stack(&[1, 2, 3, 4], &[], &[], TRACE_LENGTH);
+    stack.execute(OpCode::CSwap, OpHint::None);
+    assert_eq!(vec![3, 2, 4, 0, 0, 0, 0, 0], get_stack_state(&stack, 1));
+
+    assert_eq!(3, stack.depth);
+    assert_eq!(4, stack.max_depth);
+}
+
+#[test]
+#[should_panic(expected = "CSWAP on a non-binary condition at step 1")]
+fn cswap_fail() {
+    let mut stack = init_stack(&[2, 3, 4, 5], &[], &[], TRACE_LENGTH);
+    stack.execute(OpCode::CSwap, OpHint::None);
+}
+
+#[test]
+fn cswapw() {
+    // don't swap on false
+    let mut stack = init_stack(&[0, 2, 3, 4, 5, 6], &[], &[], TRACE_LENGTH);
+    stack.execute(OpCode::CSwapW, OpHint::None);
+    assert_eq!(vec![2, 3, 4, 5, 6, 0, 0, 0], get_stack_state(&stack, 1));
+
+    assert_eq!(5, stack.depth);
+    assert_eq!(6, stack.max_depth);
+
+    // swap on true
+    let mut stack = init_stack(&[1, 2, 3, 4, 5, 6], &[], &[], TRACE_LENGTH);
+    stack.execute(OpCode::CSwapW, OpHint::None);
+    assert_eq!(vec![4, 5, 2, 3, 6, 0, 0, 0], get_stack_state(&stack, 1));
+
+    assert_eq!(5, stack.depth);
+    assert_eq!(6, stack.max_depth);
+}
+
+#[test]
+#[should_panic(expected = "CSWAPW on a non-binary condition at step 1")]
+fn cswapw_fail() {
+    let mut stack = init_stack(&[2, 3, 4, 5, 6, 8], &[], &[], TRACE_LENGTH);
+    stack.execute(OpCode::CSwapW, OpHint::None);
+}
@@ -18,6 +18,21 @@ fn noop() {
     assert_eq!(4, stack.max_depth);
 }
 
+#[test]
+fn noop_with_stack_assertion() {
+    let mut stack = init_stack(&[1, 2, 3, 4], &[], &[], TRACE_LENGTH);
+    stack.execute(OpCode::Noop, stack_top_hint(&[1, 2, 3]));
+    assert_eq!(vec![1, 2, 3, 4, 0, 0, 0, 0], get_stack_state(&stack, 1));
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "stack assertion failed at step 1")]
+fn noop_with_stack_assertion_fail() {
+    let mut stack = init_stack(&[1, 2, 3, 4], &[], &[], TRACE_LENGTH);
+    stack.execute(OpCode::Noop, stack_top_hint(&[1, 1]));
+}
+
 #[test]
 fn assert() {
     let mut stack = init_stack(&[1, 2, 3, 4], &[], &[], TRACE_LENGTH);
@@ -99,6 +114,48 @@ fn read2() {
     assert_eq!(5, stack.max_depth);
 }
 
+#[test]
+fn read_with_tape_seed() {
+    let inputs = ProgramInputs::new(&[1], &[2], &[]).with_tape_seed(42);
+    let mut stack1 = Stack::new(&inputs, TRACE_LENGTH);
+    let mut stack2 = Stack::new(&inputs, TRACE_LENGTH);
+
+    // values from the tape are read first
+    stack1.execute(OpCode::Read, OpHint::None);
+    assert_eq!(vec![2, 1, 0, 0, 0, 0, 0, 0], get_stack_state(&stack1, 1));
+
+    // once the tapes run dry, the same pseudo-random values are read for the same seed
+    stack1.execute(OpCode::Read2, OpHint::None);
+    stack2.execute(OpCode::Read, OpHint::None);
+    stack2.execute(OpCode::Read2, OpHint::None);
+    assert_eq!(get_stack_state(&stack1, 2), get_stack_state(&stack2, 2));
+    assert_eq!(4, stack1.depth);
+}
+
+#[test]
+fn clk() {
+    let mut stack = init_stack(&[1], &[], &[], TRACE_LENGTH);
+    stack.execute(OpCode::Clk, OpHint::PushValue(BaseElement::new(5)));
+    assert_eq!(vec![5, 1, 0, 0, 0, 0, 0, 0], get_stack_state(&stack, 1));
+
+    assert_eq!(2, stack.depth);
+    assert_eq!(2, stack.max_depth);
+}
+
+#[test]
+fn sdepth() {
+    let mut stack = init_stack(&[], &[], &[], TRACE_LENGTH);
+    stack.execute(OpCode::SDepth, OpHint::None);
+    assert_eq!(vec![0, 0, 0, 0, 0, 0, 0, 0], get_stack_state(&stack, 1));
+
+    stack.execute(OpCode::SDepth, OpHint::None);
+    stack.execute(OpCode::SDepth, OpHint::None);
+    assert_eq!(vec![2, 1, 0, 0, 0, 0, 0, 0], get_stack_state(&stack, 3));
+
+    assert_eq!(3, stack.depth);
+    assert_eq!(3, stack.max_depth);
+}
+
 // STACK MANIPULATION OPERATIONS
 // ================================================================================================
 
@@ -132,6 +189,15 @@ fn dup4() {
     assert_eq!(8, stack.max_depth);
 }
 
+#[test]
+#[should_panic(
+    expected = "stack underflow at step 1: DUP4 requires 4 stack items, but stack depth is 2"
+)]
+fn dup4_underflow() {
+    let mut stack = init_stack(&[1, 2], &[], &[], TRACE_LENGTH);
+    stack.execute(OpCode::Dup4, OpHint::None);
+}
+
 #[test]
 fn pad2() {
     let mut stack = init_stack(&[1, 2], &[], &[], TRACE_LENGTH);
@@ -372,6 +438,42 @@ fn rescr() {
     assert_eq!(6, stack.max_depth);
 }
 
+// STACK WITHOUT TRACE
+// ================================================================================================
+
+#[test]
+fn stack_without_trace() {
+    let inputs = ProgramInputs::new(&[1, 2, 3, 4, 5, 6, 7, 8], &[], &[]);
+    let mut stack1 = Stack::new(&inputs, TRACE_LENGTH);
+    let mut stack2 = Stack::without_trace(&inputs);
+
+    let ops = [
+        (OpCode::Dup4, OpHint::None),
+        (OpCode::Drop4, OpHint::None),
+        (OpCode::Drop, OpHint::None),
+        (OpCode::Pad2, OpHint::None),
+        (OpCode::Swap4, OpHint::None),
+        (OpCode::Push, OpHint::PushValue(BaseElement::new(9))),
+        (OpCode::Add, OpHint::None),
+        (OpCode::Drop4, OpHint::None),
+        (OpCode::Roll4, OpHint::None),
+        (OpCode::Noop, OpHint::None),
+    ];
+
+    // states of both stacks (including slots beyond stack depth) must be the same at every step
+    for (step, &(op_code, op_hint)) in ops.iter().enumerate() {
+        stack1.execute(op_code, op_hint);
+        stack2.execute(op_code, op_hint);
+        let state = stack2
+            .get_stack_top_n(stack2.registers.len())
+            .into_iter()
+            .map(|v| v.as_int())
+            .collect::<Vec<_>>();
+        assert_eq!(get_stack_state(&stack1, step + 1), state);
+        assert_eq!(stack1.depth, stack2.depth);
+    }
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
@@ -385,6 +487,14 @@ fn init_stack(
     return Stack::new(&inputs, trace_length);
 }
 
+fn stack_top_hint(values: &[u128]) -> OpHint {
+    let mut expected = [BaseElement::ZERO; 4];
+    for (i, &value) in values.iter().enumerate() {
+        expected[i] = BaseElement::new(value);
+    }
+    OpHint::AssertStackTop(values.len() as u32, expected)
+}
+
 fn get_stack_state(stack: &Stack, step: usize) -> Vec<u128> {
     let mut state = Vec::with_capacity(stack.registers.len());
     for i in 0..stack.registers.len() {
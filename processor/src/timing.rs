@@ -0,0 +1,104 @@
+use super::{ExecutionHooks, ExecutionState, OpCode};
+use core::fmt;
+use std::time::{Duration, Instant};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of distinct values an operation code can take; operation codes are 8-bit values.
+const NUM_OP_CODES: usize = 256;
+
+// OPERATION TIMINGS
+// ================================================================================================
+
+/// Execution hooks which measure wall-clock time spent executing each type of user operation.
+///
+/// Time is measured from the moment the processor starts decoding an operation until the stack
+/// finishes executing it; time spent on control flow (starting and closing blocks) is not
+/// attributed to any operation. Measurements include the overhead of reading the clock, and
+/// thus, are meaningful only in relation to each other.
+pub struct OpTimings {
+    timings: [Option<OpTiming>; NUM_OP_CODES],
+    op_start: Option<Instant>,
+}
+
+/// Number of times an operation was executed and the total time spent executing it.
+#[derive(Copy, Clone, Debug)]
+pub struct OpTiming {
+    pub op_code: OpCode,
+    pub count: usize,
+    pub total: Duration,
+}
+
+impl OpTimings {
+    /// Returns empty timings.
+    pub fn new() -> Self {
+        OpTimings {
+            timings: [None; NUM_OP_CODES],
+            op_start: None,
+        }
+    }
+
+    /// Returns timings of all executed operations sorted by total execution time, starting with
+    /// the operation on which the most time was spent.
+    pub fn report(&self) -> Vec<OpTiming> {
+        let mut report = self.timings.iter().flatten().copied().collect::<Vec<_>>();
+        report.sort_by(|a, b| b.total.cmp(&a.total));
+        report
+    }
+}
+
+impl Default for OpTimings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ExecutionHooks for OpTimings {
+    fn before_op(&mut self, _op_code: OpCode, _state: &ExecutionState) {
+        self.op_start = Some(Instant::now());
+    }
+
+    fn after_op(&mut self, op_code: OpCode, _state: &ExecutionState) {
+        let elapsed = match self.op_start.take() {
+            Some(start) => start.elapsed(),
+            None => return,
+        };
+
+        let timing = &mut self.timings[op_code as usize];
+        match timing {
+            Some(timing) => {
+                timing.count += 1;
+                timing.total += elapsed;
+            }
+            None => {
+                *timing = Some(OpTiming {
+                    op_code,
+                    count: 1,
+                    total: elapsed,
+                })
+            }
+        }
+    }
+}
+
+impl fmt::Display for OpTimings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<10} {:>10} {:>12} {:>10}",
+            "operation", "count", "total (us)", "avg (ns)"
+        )?;
+        for timing in self.report() {
+            writeln!(
+                f,
+                "{:<10} {:>10} {:>12} {:>10}",
+                timing.op_code.to_string(),
+                timing.count,
+                timing.total.as_micros(),
+                timing.total.as_nanos() / timing.count as u128
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -1,8 +1,10 @@
-use air::{ProcessorAir, PublicInputs};
+use air::ProcessorAir;
 
 // EXPORTS
 // ================================================================================================
 
+pub use air::{PublicInputs, PublicInputsError};
+#[cfg(feature = "assembly")]
 pub use assembly;
 pub use winterfell::{StarkProof, VerifierError};
 
@@ -15,13 +17,117 @@ pub use winterfell::{StarkProof, VerifierError};
 /// provided `public_inputs` and some secret inputs, and the result is equal to the `outputs`.
 ///
 /// # Errors
-/// Returns an error if the provided proof does not prove a correct execution of the program.
+/// Returns an error if the program hash, public inputs, or outputs are not valid (see
+/// [PublicInputs::try_new()]), or if the provided proof does not prove a correct execution of the
+/// program.
 pub fn verify(
     program_hash: [u8; 32],
     public_inputs: &[u128],
     outputs: &[u128],
     proof: StarkProof,
 ) -> Result<(), VerifierError> {
-    let pub_inputs = PublicInputs::new(program_hash, public_inputs, outputs);
+    let pub_inputs = PublicInputs::try_new(program_hash, public_inputs, outputs)
+        .map_err(invalid_public_inputs)?;
+    verify_public_inputs(pub_inputs, proof)
+}
+
+/// Returns Ok(()) if the specified proof proves correct execution of a program described by
+/// `pub_inputs` (i.e. a program with the specified hash executed against the specified inputs
+/// produced the specified outputs).
+///
+/// # Errors
+/// Returns an error if the provided proof does not prove a correct execution of the program.
+pub fn verify_public_inputs(
+    pub_inputs: PublicInputs,
+    proof: StarkProof,
+) -> Result<(), VerifierError> {
     winterfell::verify::<ProcessorAir>(proof, pub_inputs)
 }
+
+/// Returns Ok(()) if all proofs in the specified list prove correct execution of their respective
+/// programs against the specified inputs and outputs.
+///
+/// This is a convenience loop which verifies every proof independently via [verify()]; no work
+/// is shared between proofs, and thus, verifying the list takes as long as verifying each of its
+/// proofs one by one. Proofs may be generated for the same program or for different programs.
+/// All proofs are verified even if some of them are invalid.
+///
+/// # Errors
+/// Returns indexes of all proofs in the list which failed verification together with the
+/// reasons for the failures, in the order in which the proofs appear in the list. Invalid
+/// public inputs or outputs of a proof are reported as a failure of that proof.
+pub fn verify_each(bundles: Vec<ProofBundle>) -> Result<(), Vec<(usize, VerifierError)>> {
+    let failures = bundles
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, bundle)| {
+            verify(
+                bundle.program_hash,
+                &bundle.inputs,
+                &bundle.outputs,
+                bundle.proof,
+            )
+            .err()
+            .map(|err| (i, err))
+        })
+        .collect::<Vec<_>>();
+
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(failures)
+    }
+}
+
+// PROOF BUNDLE
+// ================================================================================================
+
+/// A proof of program execution together with all data needed to verify it.
+pub struct ProofBundle {
+    program_hash: [u8; 32],
+    inputs: Vec<u128>,
+    outputs: Vec<u128>,
+    proof: StarkProof,
+}
+
+impl ProofBundle {
+    /// Returns a new proof bundle instantiated from the specified program hash, public inputs,
+    /// outputs, and a proof of program execution.
+    pub fn new(
+        program_hash: [u8; 32],
+        public_inputs: &[u128],
+        outputs: &[u128],
+        proof: StarkProof,
+    ) -> Self {
+        Self {
+            program_hash,
+            inputs: public_inputs.to_vec(),
+            outputs: outputs.to_vec(),
+            proof,
+        }
+    }
+
+    /// Returns hash of the program this bundle is for.
+    pub fn program_hash(&self) -> &[u8; 32] {
+        &self.program_hash
+    }
+
+    /// Returns public inputs against which the program was executed.
+    pub fn public_inputs(&self) -> &[u128] {
+        &self.inputs
+    }
+
+    /// Returns outputs produced by the program.
+    pub fn outputs(&self) -> &[u128] {
+        &self.outputs
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Converts invalid public inputs into a verifier error; [VerifierError] has no dedicated variant
+/// for invalid public inputs, and thus, such inputs are reported as a deserialization failure.
+fn invalid_public_inputs(err: PublicInputsError) -> VerifierError {
+    VerifierError::ProofDeserializationError(format!("invalid public inputs: {}", err))
+}